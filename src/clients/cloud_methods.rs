@@ -1,7 +1,10 @@
 pub use crate::clients::SolrCoreMethods;
+use crate::collections_admin::{SolrClusterStatus, SolrCollectionsAction};
 pub use crate::config::SolrClientConfig;
+use crate::configsets::SolrConfigSetUpload;
 pub use crate::errors::{SolrError, SolrResult};
 pub use crate::requests::SolrRequestBuilder;
+use crate::requests::{SolrCsvOptions, SolrExtractOptions};
 
 #[cfg(not(feature = "blocking"))]
 use reqwest::RequestBuilder;
@@ -51,14 +54,196 @@ use reqwest::blocking::RequestBuilder;
 pub trait SolrCloudMethods: SolrCoreMethods {
     /// Create a requestBuilder for a solrCloud select request
     ///
-    /// This method assumes the use of a GET method, and does not support POST currently.
+    /// This method assumes the use of a GET method, and does not support POST currently. The
+    /// collection can also be an alias name, which solr resolves (querying across all of the
+    /// alias's collections).
     fn select(&self, collection: &str) -> SolrResult<RequestBuilder> {
         self.create_get_request(&format!("{}/{}", collection, "select"))
     }
 
     /// Create a requestBuilder for a solrCloud update request (using HTTP POST)
+    ///
+    /// The collection can also be an alias name: solr sends updates to the alias's first
+    /// collection, or for routed aliases, to the collection matching each document.
     fn update(&self, collection: &str) -> SolrResult<RequestBuilder> {
         self.create_post_request(&format!("{}/{}", collection, "update"))
             .map(|x| x.content_type("application/json"))
     }
+
+    /// Create a requestBuilder for the /update/json/docs handler (using HTTP POST)
+    ///
+    /// This handler accepts a bare document, an array of documents, or newline separated
+    /// documents, so pairs well with `SolrRequestBuilder::payload_iter` and
+    /// `PayloadFormat::JsonLines` for large batches.
+    fn update_json_docs(&self, collection: &str) -> SolrResult<RequestBuilder> {
+        self.create_post_request(&format!("{}/{}", collection, "update/json/docs"))
+            .map(|x| x.content_type("application/json"))
+    }
+
+    /// Create a requestBuilder for the CSV update handler (using HTTP POST)
+    ///
+    /// The CSV data itself should be supplied as the request body, eg. with `.body(csv_text)`.
+    fn update_csv(
+        &self,
+        collection: &str,
+        csv_options: &SolrCsvOptions,
+    ) -> SolrResult<RequestBuilder> {
+        self.create_post_request(&format!("{}/{}", collection, "update/csv"))
+            .map(|x| x.content_type("application/csv"))
+            .map(|x| x.query(&csv_options.query_params()))
+    }
+
+    /// Create a requestBuilder for an XML update request (using HTTP POST)
+    ///
+    /// Use `SolrRequestBuilder::xml_payload` to build the body from serializable documents.
+    fn update_xml(&self, collection: &str) -> SolrResult<RequestBuilder> {
+        self.create_post_request(&format!("{}/{}", collection, "update"))
+            .map(|x| x.content_type("application/xml"))
+    }
+
+    /// Create a requestBuilder for the Solr Cell extracting request handler (using HTTP POST)
+    ///
+    /// The file should be supplied either as a multipart upload (eg. with
+    /// `SolrRequestBuilder::multipart_file`), or as a raw body with its Content-Type set. With
+    /// extract_only set, the response can be deserialised into a SolrExtractOnlyType.
+    ///
+    /// ```no_run
+    /// # use tokio::runtime::Runtime;
+    /// use stellr::prelude::*;
+    /// use stellr::response_types::SolrExtractOnlyType;
+    /// use stellr::{DirectSolrClient, SolrExtractOptions};
+    ///
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let extract_options = SolrExtractOptions {
+    ///     extract_only: Some(true),
+    ///     ..Default::default()
+    /// };
+    /// let file_contents = std::fs::read("report.pdf")?;
+    ///
+    /// let solr_client = DirectSolrClient::new("http://localhost:8983/solr")?;
+    /// let solr_request = solr_client
+    ///     .extract("films", &extract_options)?
+    ///     .multipart_file("report.pdf", file_contents);
+    ///
+    /// # let mut rt = Runtime::new().unwrap();
+    /// let result_struct =
+    /// # rt.block_on(async {
+    ///     solr_request
+    ///         .call::<SolrExtractOnlyType>()
+    ///         .await
+    /// # })
+    ///     ?;
+    ///
+    /// println!("{:?}", result_struct.metadata.get("Content-Type"));
+    /// # Ok(()) }
+    /// ```
+    fn extract(
+        &self,
+        collection: &str,
+        extract_options: &SolrExtractOptions,
+    ) -> SolrResult<RequestBuilder> {
+        self.create_post_request(&format!("{}/{}", collection, "update/extract"))
+            .map(|x| x.query(&extract_options.query_params()))
+    }
+
+    /// Create a requestBuilder for a Collections API action (/admin/collections)
+    ///
+    /// The response can be deserialised into the action's Response type, or use
+    /// `collections_admin::run_collections_action` to send the request and parse it in one step.
+    ///
+    /// ```no_run
+    /// # use tokio::runtime::Runtime;
+    /// use stellr::prelude::*;
+    /// use stellr::collections_admin::SolrReloadCollection;
+    /// use stellr::response_types::SolrCollectionsAdminType;
+    ///
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let solr_client = stellr::DirectSolrClient::new("http://localhost:8983/solr")?;
+    /// let solr_request = solr_client.collections_admin(&SolrReloadCollection::new("films"))?;
+    ///
+    /// # let mut rt = Runtime::new().unwrap();
+    /// let result_struct =
+    /// # rt.block_on(async {
+    ///     solr_request
+    ///         .call::<SolrCollectionsAdminType>()
+    ///         .await
+    /// # })
+    ///     ?;
+    ///
+    /// assert!(result_struct.is_success());
+    /// # Ok(()) }
+    /// ```
+    fn collections_admin<A: SolrCollectionsAction>(&self, action: &A) -> SolrResult<RequestBuilder>
+    where
+        Self: Sized,
+    {
+        self.create_get_request("admin/collections")
+            .map(|x| x.query(&[("action", action.action()), ("wt", "json")]))
+            .map(|x| x.query(&action.query_params()))
+    }
+
+    /// Create a requestBuilder for the status of the whole cluster (CLUSTERSTATUS)
+    ///
+    /// The response can be deserialised into a SolrClusterStatusType, to give a typed model of
+    /// collections, shards, replicas, aliases, roles and live nodes.
+    ///
+    /// ```no_run
+    /// # use tokio::runtime::Runtime;
+    /// use stellr::prelude::*;
+    /// use stellr::response_types::SolrClusterStatusType;
+    ///
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let solr_client = stellr::DirectSolrClient::new("http://localhost:8983/solr")?;
+    ///
+    /// # let mut rt = Runtime::new().unwrap();
+    /// let cluster_status =
+    /// # rt.block_on(async {
+    ///     solr_client
+    ///         .cluster_status()?
+    ///         .call::<SolrClusterStatusType>()
+    ///         .await
+    /// # })
+    ///     ?;
+    ///
+    /// for (collection, shard, replica) in cluster_status.cluster.unhealthy_replicas() {
+    ///     println!("{}/{}/{} is unhealthy", collection, shard, replica);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    fn cluster_status(&self) -> SolrResult<RequestBuilder>
+    where
+        Self: Sized,
+    {
+        self.collections_admin(&SolrClusterStatus::default())
+    }
+
+    /// Create a requestBuilder to upload a configset (using HTTP POST)
+    ///
+    /// The zipped configset (eg. from `configsets::zip_directory`) should be supplied as the
+    /// request body. The response can be deserialised into a SolrCollectionsAdminType.
+    fn upload_configset(&self, upload: &SolrConfigSetUpload) -> SolrResult<RequestBuilder> {
+        self.create_post_request("admin/configs")
+            .map(|x| x.content_type("application/octet-stream"))
+            .map(|x| x.query(&[("wt", "json")]))
+            .map(|x| x.query(&upload.query_params()))
+    }
+
+    /// Create a requestBuilder to list all configsets
+    ///
+    /// The response can be deserialised into a SolrListConfigSetsType.
+    fn list_configsets(&self) -> SolrResult<RequestBuilder> {
+        self.create_get_request("admin/configs")
+            .map(|x| x.query(&[("action", "LIST"), ("wt", "json")]))
+    }
+
+    /// Create a requestBuilder to delete a configset, which must not be in use by any collection
+    ///
+    /// The response can be deserialised into a SolrCollectionsAdminType.
+    fn delete_configset(&self, name: &str) -> SolrResult<RequestBuilder> {
+        self.create_get_request("admin/configs")
+            .map(|x| x.query(&[("action", "DELETE"), ("name", name), ("wt", "json")]))
+    }
 }
@@ -1,5 +1,13 @@
 pub use crate::config::SolrClientConfig;
+use crate::config_api::{SolrConfigCommands, SolrParamSetChanges};
+use crate::core_admin::SolrCoreAdminAction;
 pub use crate::errors::{SolrError, SolrResult};
+use crate::managed_resources::SolrSynonymMappings;
+use crate::metrics::SolrMetricsOptions;
+use crate::replication::SolrReplicationCommand;
+use crate::requests::{SolrLukeOptions, SolrRequestBuilder};
+use crate::schema::SolrSchemaChanges;
+use crate::security::{SolrAuthenticationCommands, SolrAuthorizationCommands};
 
 #[cfg(not(feature = "blocking"))]
 use reqwest::{Client, ClientBuilder, RequestBuilder};
@@ -7,6 +15,7 @@ use reqwest::{Client, ClientBuilder, RequestBuilder};
 #[cfg(feature = "blocking")]
 use reqwest::blocking::{Client, ClientBuilder, RequestBuilder};
 
+use serde_json::json;
 use tracing::debug;
 
 /// Add a set of helper methods for "classic Solr" (non-cloud) targets.
@@ -97,6 +106,287 @@ pub trait SolrCoreMethods {
         let base_url = self.build_request_url(path)?;
         Ok(client.post(&base_url))
     }
+
+    /// Generate a PUT request builder, based on the base_url from the client and a path from the
+    /// caller
+    fn create_put_request(&self, path: &str) -> SolrResult<RequestBuilder> {
+        let client = self.build_client()?;
+        let base_url = self.build_request_url(path)?;
+        Ok(client.put(&base_url))
+    }
+
+    /// Generate a DELETE request builder, based on the base_url from the client and a path from
+    /// the caller
+    fn create_delete_request(&self, path: &str) -> SolrResult<RequestBuilder> {
+        let client = self.build_client()?;
+        let base_url = self.build_request_url(path)?;
+        Ok(client.delete(&base_url))
+    }
+
+    /// Create a requestBuilder for a Core Admin API action
+    ///
+    /// The response can be deserialised with the action's `Response` type, or the request can be
+    /// sent directly with `core_admin::run_core_admin_action`.
+    fn core_admin<A: SolrCoreAdminAction>(&self, action: &A) -> SolrResult<RequestBuilder>
+    where
+        Self: Sized,
+    {
+        self.create_get_request("admin/cores")
+            .map(|x| x.query(&[("action", action.action()), ("wt", "json")]))
+            .map(|x| x.query(&action.query_params()))
+    }
+
+    /// Create a requestBuilder for a command to a core's replication handler
+    ///
+    /// The response can be deserialised with the command's `Response` type, or the request can be
+    /// sent directly with `replication::run_replication_command`.
+    fn replication<R: SolrReplicationCommand>(
+        &self,
+        core: &str,
+        command: &R,
+    ) -> SolrResult<RequestBuilder>
+    where
+        Self: Sized,
+    {
+        self.create_get_request(&format!("{}/{}", core, "replication"))
+            .map(|x| x.query(&[("command", command.command()), ("wt", "json")]))
+            .map(|x| x.query(&command.query_params()))
+    }
+
+    /// Create a requestBuilder to ping a core (or collection), which runs its ping query
+    ///
+    /// Responses match `response_types::SolrPingType`.
+    fn ping(&self, core: &str) -> SolrResult<RequestBuilder> {
+        self.create_get_request(&format!("{}/{}", core, "admin/ping"))
+            .map(|x| x.query(&[("wt", "json")]))
+    }
+
+    /// Create a requestBuilder for the node's health check (SolrCloud only)
+    ///
+    /// With require_healthy_cores, the node is only reported healthy once all of its cores are
+    /// active, which suits readiness probes. Responses match `response_types::SolrHealthType`.
+    fn health(&self, require_healthy_cores: bool) -> SolrResult<RequestBuilder> {
+        self.create_get_request("admin/info/health")
+            .map(|x| x.query(&[("requireHealthyCores", require_healthy_cores)]))
+            .map(|x| x.query(&[("wt", "json")]))
+    }
+
+    /// Create a requestBuilder for the node's system information (versions, JVM and memory)
+    ///
+    /// Responses match `response_types::SolrSystemInfoType`.
+    fn system_info(&self) -> SolrResult<RequestBuilder> {
+        self.create_get_request("admin/info/system")
+            .map(|x| x.query(&[("wt", "json")]))
+    }
+
+    /// Create a requestBuilder for the node's metrics, filtered by a SolrMetricsOptions
+    ///
+    /// Responses match `response_types::SolrMetricsType`.
+    fn metrics(&self, options: &SolrMetricsOptions) -> SolrResult<RequestBuilder> {
+        self.create_get_request("admin/metrics")
+            .map(|x| x.query(&[("wt", "json")]))
+            .map(|x| x.query(&options.query_params()))
+    }
+
+    /// Create a requestBuilder to read the authentication section of security.json
+    ///
+    /// Responses match `response_types::SolrAuthenticationType`.
+    fn authentication(&self) -> SolrResult<RequestBuilder> {
+        self.create_get_request("admin/authentication")
+            .map(|x| x.query(&[("wt", "json")]))
+    }
+
+    /// Create a requestBuilder to add, change or delete BasicAuth users
+    ///
+    /// Responses match `response_types::SolrUpdateType`.
+    fn update_authentication(
+        &self,
+        commands: &SolrAuthenticationCommands,
+    ) -> SolrResult<RequestBuilder> {
+        let json_payload = serde_json::to_string(commands)?;
+        self.create_post_request("admin/authentication")
+            .map(|x| x.content_type("application/json").body(json_payload))
+    }
+
+    /// Create a requestBuilder to read the authorization section of security.json
+    ///
+    /// Responses match `response_types::SolrAuthorizationType`.
+    fn authorization(&self) -> SolrResult<RequestBuilder> {
+        self.create_get_request("admin/authorization")
+            .map(|x| x.query(&[("wt", "json")]))
+    }
+
+    /// Create a requestBuilder to change user roles and permissions
+    ///
+    /// Responses match `response_types::SolrUpdateType`.
+    fn update_authorization(
+        &self,
+        commands: &SolrAuthorizationCommands,
+    ) -> SolrResult<RequestBuilder> {
+        let json_payload = serde_json::to_string(commands)?;
+        self.create_post_request("admin/authorization")
+            .map(|x| x.content_type("application/json").body(json_payload))
+    }
+
+    /// Create a requestBuilder for the Luke request handler, to inspect a core's index
+    ///
+    /// Responses match `response_types::SolrLukeType`.
+    fn luke(&self, core: &str, options: &SolrLukeOptions) -> SolrResult<RequestBuilder> {
+        self.create_get_request(&format!("{}/{}", core, "admin/luke"))
+            .map(|x| x.query(&[("wt", "json")]))
+            .map(|x| x.query(&options.query_params()))
+    }
+
+    /// Create a requestBuilder to read a core's (or collection's) whole schema, via the Schema API
+    ///
+    /// The response can be deserialised into `response_types::SolrSchemaType`.
+    fn schema(&self, core: &str) -> SolrResult<RequestBuilder> {
+        self.create_get_request(&format!("{}/{}", core, "schema"))
+    }
+
+    /// Create a requestBuilder to apply a set of schema changes, in a single Schema API request
+    ///
+    /// Solr applies the changes in order, and rejects all of them if any one fails, with the
+    /// failing commands listed in the error's details. Responses match
+    /// `response_types::SolrUpdateType`.
+    fn update_schema(&self, core: &str, changes: &SolrSchemaChanges) -> SolrResult<RequestBuilder> {
+        let json_payload = serde_json::to_string(changes)?;
+        self.create_post_request(&format!("{}/{}", core, "schema"))
+            .map(|x| x.content_type("application/json").body(json_payload))
+    }
+
+    /// Create a requestBuilder to read a core's effective configuration, via the Config API
+    ///
+    /// Responses match `response_types::SolrConfigType`.
+    fn config(&self, core: &str) -> SolrResult<RequestBuilder> {
+        self.create_get_request(&format!("{}/{}", core, "config"))
+    }
+
+    /// Create a requestBuilder to read only the Config API's overrides (the config overlay)
+    ///
+    /// Responses match `response_types::SolrConfigOverlayType`.
+    fn config_overlay(&self, core: &str) -> SolrResult<RequestBuilder> {
+        self.create_get_request(&format!("{}/{}", core, "config/overlay"))
+    }
+
+    /// Create a requestBuilder to apply a set of Config API commands, in order
+    ///
+    /// Responses match `response_types::SolrUpdateType`.
+    fn update_config(
+        &self,
+        core: &str,
+        commands: &SolrConfigCommands,
+    ) -> SolrResult<RequestBuilder> {
+        let json_payload = serde_json::to_string(commands)?;
+        self.create_post_request(&format!("{}/{}", core, "config"))
+            .map(|x| x.content_type("application/json").body(json_payload))
+    }
+
+    /// Create a requestBuilder to read a core's param sets, via the Request Parameters API
+    ///
+    /// Responses match `response_types::SolrParamSetsType`.
+    fn config_params(&self, core: &str) -> SolrResult<RequestBuilder> {
+        self.create_get_request(&format!("{}/{}", core, "config/params"))
+    }
+
+    /// Create a requestBuilder to create, update or delete param sets
+    ///
+    /// Responses match `response_types::SolrUpdateType`.
+    fn update_params(
+        &self,
+        core: &str,
+        changes: &SolrParamSetChanges,
+    ) -> SolrResult<RequestBuilder> {
+        let json_payload = serde_json::to_string(changes)?;
+        self.create_post_request(&format!("{}/{}", core, "config/params"))
+            .map(|x| x.content_type("application/json").body(json_payload))
+    }
+
+    /// Create a requestBuilder to read a managed synonym map (eg. "english")
+    ///
+    /// Responses match `response_types::SolrManagedSynonymsType`.
+    fn managed_synonyms(&self, core: &str, name: &str) -> SolrResult<RequestBuilder> {
+        self.create_get_request(&managed_path(core, "synonyms", name))
+            .map(|x| x.query(&[("wt", "json")]))
+    }
+
+    /// Create a requestBuilder to add synonym mappings to a managed synonym map
+    ///
+    /// Targets are merged into any existing mapping for a term, rather than replacing it.
+    /// Changes only apply to analysis once the collection is reloaded. Responses match
+    /// `response_types::SolrUpdateType`.
+    fn add_synonyms(
+        &self,
+        core: &str,
+        name: &str,
+        mappings: &SolrSynonymMappings,
+    ) -> SolrResult<RequestBuilder> {
+        let json_payload = serde_json::to_string(mappings)?;
+        self.create_put_request(&managed_path(core, "synonyms", name))
+            .map(|x| x.content_type("application/json").body(json_payload))
+    }
+
+    /// Create a requestBuilder to delete a term's mapping from a managed synonym map
+    fn delete_synonym(&self, core: &str, name: &str, term: &str) -> SolrResult<RequestBuilder> {
+        self.create_delete_request(&format!(
+            "{}/{}",
+            managed_path(core, "synonyms", name),
+            path_segment(term)
+        ))
+    }
+
+    /// Create a requestBuilder to change whether a managed synonym map ignores case
+    fn set_synonyms_ignore_case(
+        &self,
+        core: &str,
+        name: &str,
+        ignore_case: bool,
+    ) -> SolrResult<RequestBuilder> {
+        let json_payload = json!({"initArgs": {"ignoreCase": ignore_case}}).to_string();
+        self.create_put_request(&managed_path(core, "synonyms", name))
+            .map(|x| x.content_type("application/json").body(json_payload))
+    }
+
+    /// Create a requestBuilder to read a managed stopword list (eg. "english")
+    ///
+    /// Responses match `response_types::SolrManagedStopwordsType`.
+    fn managed_stopwords(&self, core: &str, name: &str) -> SolrResult<RequestBuilder> {
+        self.create_get_request(&managed_path(core, "stopwords", name))
+            .map(|x| x.query(&[("wt", "json")]))
+    }
+
+    /// Create a requestBuilder to add words to a managed stopword list
+    ///
+    /// Changes only apply to analysis once the collection is reloaded. Responses match
+    /// `response_types::SolrUpdateType`.
+    fn add_stopwords(&self, core: &str, name: &str, words: &[&str]) -> SolrResult<RequestBuilder> {
+        let json_payload = serde_json::to_string(words)?;
+        self.create_put_request(&managed_path(core, "stopwords", name))
+            .map(|x| x.content_type("application/json").body(json_payload))
+    }
+
+    /// Create a requestBuilder to delete a word from a managed stopword list
+    fn delete_stopword(&self, core: &str, name: &str, word: &str) -> SolrResult<RequestBuilder> {
+        self.create_delete_request(&format!(
+            "{}/{}",
+            managed_path(core, "stopwords", name),
+            path_segment(word)
+        ))
+    }
+
+    /// Create a requestBuilder to change whether a managed stopword list ignores case
+    ///
+    /// Solr refuses to stop ignoring case once the list holds any words.
+    fn set_stopwords_ignore_case(
+        &self,
+        core: &str,
+        name: &str,
+        ignore_case: bool,
+    ) -> SolrResult<RequestBuilder> {
+        let json_payload = json!({"initArgs": {"ignoreCase": ignore_case}}).to_string();
+        self.create_put_request(&managed_path(core, "stopwords", name))
+            .map(|x| x.content_type("application/json").body(json_payload))
+    }
 }
 
 /// Adds methods to build an HTTP client configured according to a SolrClientConfig.
@@ -121,3 +411,19 @@ impl SolrClientBuilder for ClientBuilder {
             .connection_verbose(config.verbose)
     }
 }
+
+fn managed_path(core: &str, kind: &str, name: &str) -> String {
+    format!("{}/schema/analysis/{}/{}", core, kind, path_segment(name))
+}
+
+/// Percent-encode a term for use as a single URL path segment
+fn path_segment(term: &str) -> String {
+    term.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
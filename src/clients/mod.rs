@@ -1,6 +1,7 @@
 pub mod cloud_methods;
 pub mod core_methods;
 pub mod direct_solr_client;
+mod routing;
 pub mod zk_solr_client;
 
 #[doc(inline)]
@@ -0,0 +1,290 @@
+use serde_json::Value;
+
+use crate::errors::{SolrError, SolrResult};
+use crate::response_types::{SolrCollectionState, SolrShardState};
+
+/// MurmurHash3 (x86, 32 bit), as used by solr's compositeId router
+pub(crate) fn murmurhash3_x86_32(data: &[u8], seed: u32) -> u32 {
+    const C1: u32 = 0xcc9e_2d51;
+    const C2: u32 = 0x1b87_3593;
+
+    let mut h1 = seed;
+    let chunks = data.chunks_exact(4);
+    let tail = chunks.remainder();
+
+    for chunk in chunks {
+        let mut k1 = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        k1 = k1.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2);
+
+        h1 ^= k1;
+        h1 = h1.rotate_left(13).wrapping_mul(5).wrapping_add(0xe654_6b64);
+    }
+
+    if !tail.is_empty() {
+        let mut k1 = 0u32;
+        for (i, byte) in tail.iter().enumerate() {
+            k1 ^= u32::from(*byte) << (8 * i);
+        }
+        h1 ^= k1.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2);
+    }
+
+    h1 ^= data.len() as u32;
+    h1 ^= h1 >> 16;
+    h1 = h1.wrapping_mul(0x85eb_ca6b);
+    h1 ^= h1 >> 13;
+    h1 = h1.wrapping_mul(0xc2b2_ae35);
+    h1 ^= h1 >> 16;
+    h1
+}
+
+fn string_hash(key: &str) -> i32 {
+    murmurhash3_x86_32(key.as_bytes(), 0) as i32
+}
+
+/// Hash a document id (or _route_ value) the same way as solr's compositeId router
+///
+/// Ids can be prefixed with up to two routing keys, eg. `tenant!id` or `tenant!user!id`, and each
+/// prefix can set how many bits of the hash it controls, eg. `tenant/4!id`.
+pub(crate) fn composite_id_hash(key: &str) -> i32 {
+    // a leading separator still counts, giving an empty first part
+    let first = match key.find('!') {
+        Some(first) => first,
+        None => return string_hash(key),
+    };
+
+    let mut parts: Vec<&str> = vec![&key[..first]];
+    let last = key.len() - 1;
+    // solr keeps the separator on the end of the middle part
+    if first < last {
+        match key[first + 1..].find('!').map(|pos| pos + first + 1) {
+            None => parts.push(&key[first + 1..]),
+            Some(second) if second == last => {
+                if first < second - 1 {
+                    parts.push(&key[first + 1..=second]);
+                }
+            }
+            Some(second) => {
+                parts.push(&key[first + 1..=second]);
+                parts.push(&key[second + 1..]);
+            }
+        }
+    }
+
+    let mut pieces = parts.len();
+    if key.ends_with('!') && pieces < 3 {
+        pieces += 1;
+    }
+
+    let tri_level = pieces == 3;
+    let mut num_bits = if tri_level { [8, 8] } else { [16, 0] };
+    let mut hashes = Vec::with_capacity(pieces);
+
+    for (i, bits) in num_bits.iter_mut().enumerate().take(pieces - 1) {
+        if let Some(pos) = parts[i].find('/').filter(|pos| *pos > 0) {
+            *bits = parts[i][pos + 1..]
+                .parse::<u32>()
+                .map(|bits| bits.min(16))
+                .unwrap_or(*bits);
+            parts[i] = &parts[i][..pos];
+        }
+    }
+    for i in 0..pieces {
+        hashes.push(string_hash(parts.get(i).copied().unwrap_or("")));
+    }
+
+    let masks = if tri_level {
+        let first = high_bits_mask(num_bits[0]);
+        let second = high_bits_mask(num_bits[0] + num_bits[1]) ^ first;
+        let rest = if num_bits[0] + num_bits[1] == 32 {
+            0
+        } else {
+            !(first | second)
+        };
+        vec![first, second, rest]
+    } else {
+        let first = high_bits_mask(num_bits[0]);
+        vec![first, !first]
+    };
+
+    hashes
+        .iter()
+        .zip(masks.iter())
+        .fold(0, |result, (hash, mask)| result | (hash & mask))
+}
+
+/// Mask covering the top `bits` bits of an i32
+fn high_bits_mask(bits: u32) -> i32 {
+    if bits == 0 {
+        0
+    } else {
+        (-1i32).wrapping_shl(32 - bits)
+    }
+}
+
+/// Find the active shard which owns a routing key (a document id, or _route_ value)
+///
+/// For the implicit router, the routing key is the shard name itself.
+pub(crate) fn target_shard<'a>(
+    state: &'a SolrCollectionState,
+    route_key: &str,
+) -> SolrResult<(&'a String, &'a SolrShardState)> {
+    match state.router.name.as_str() {
+        "compositeId" => {
+            let hash = composite_id_hash(route_key);
+            state
+                .shards
+                .iter()
+                .filter(|(_, shard)| shard.is_active())
+                .find(|(_, shard)| {
+                    shard
+                        .hash_range()
+                        .map(|(min, max)| min <= hash && hash <= max)
+                        .unwrap_or(false)
+                })
+                .ok_or_else(|| {
+                    SolrError::RoutingError(format!("no active shard found for {}", route_key))
+                })
+        }
+        "implicit" => state
+            .shards
+            .get_key_value(route_key)
+            .filter(|(_, shard)| shard.is_active())
+            .ok_or_else(|| SolrError::RoutingError(format!("no active shard named {}", route_key))),
+        other => Err(SolrError::RoutingError(format!(
+            "unsupported router {}",
+            other
+        ))),
+    }
+}
+
+/// Find the core URL of the leader of the shard owning a routing key
+pub(crate) fn leader_url(
+    state: &SolrCollectionState,
+    route_key: &str,
+    url_scheme: &str,
+) -> SolrResult<String> {
+    let (shard_name, shard) = target_shard(state, route_key)?;
+    shard
+        .leader()
+        .filter(|replica| replica.is_active())
+        .map(|replica| replica.core_url(url_scheme))
+        .ok_or_else(|| SolrError::RoutingError(format!("no active leader for {}", shard_name)))
+}
+
+/// Extract the routing key from a serialized document
+///
+/// This is the collection's router.field if set, or else the uniqueKey field.
+pub(crate) fn document_route_key(
+    state: &SolrCollectionState,
+    id_field: &str,
+    document: &Value,
+) -> SolrResult<String> {
+    let field = state.router.field.as_deref().unwrap_or(id_field);
+    match document.get(field) {
+        Some(Value::String(key)) => Ok(key.clone()),
+        Some(Value::Number(key)) => Ok(key.to_string()),
+        _ => Err(SolrError::RoutingError(format!(
+            "document has no {} field to route on",
+            field
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn test_state() -> SolrCollectionState {
+        serde_json::from_value(serde_json::json!({
+            "router": {"name": "compositeId"},
+            "shards": {
+                "shard1": {
+                    "range": "80000000-ffffffff",
+                    "state": "active",
+                    "replicas": {
+                        "core_node3": {
+                            "core": "films_shard1_replica_n1",
+                            "base_url": "http://10.0.0.1:8983/solr",
+                            "node_name": "10.0.0.1:8983_solr",
+                            "state": "active",
+                            "type": "NRT",
+                            "leader": "true"
+                        }
+                    }
+                },
+                "shard2": {
+                    "range": "0-7fffffff",
+                    "state": "active",
+                    "replicas": {
+                        "core_node4": {
+                            "core": "films_shard2_replica_n2",
+                            "node_name": "10.0.0.2:8983_solr",
+                            "state": "active",
+                            "type": "NRT",
+                            "leader": "true"
+                        }
+                    }
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_murmurhash3_known_values() {
+        assert_eq!(murmurhash3_x86_32(b"", 0), 0);
+        assert_eq!(murmurhash3_x86_32(b"", 1), 0x514e_28b7);
+        assert_eq!(murmurhash3_x86_32(b"hello", 0), 0x248b_fa47);
+        assert_eq!(murmurhash3_x86_32(b"Hello, world!", 1234), 0xfaf6_cdb3);
+    }
+
+    #[test]
+    fn test_composite_id_prefixes() {
+        let tenant = string_hash("tenant") as u32;
+        let plain = string_hash("doc1") as u32;
+
+        assert_eq!(composite_id_hash("doc1") as u32, plain);
+        assert_eq!(
+            composite_id_hash("tenant!doc1") as u32,
+            (tenant & 0xffff_0000) | (plain & 0x0000_ffff)
+        );
+        assert_eq!(
+            composite_id_hash("tenant/4!doc1") as u32,
+            (tenant & 0xf000_0000) | (plain & 0x0fff_ffff)
+        );
+        // an empty prefix hashes to zero, rather than the whole id being hashed
+        assert_eq!(composite_id_hash("!doc1") as u32, plain & 0x0000_ffff);
+        // a bare prefix (as used for _route_) covers the same top bits as its documents
+        assert_eq!(
+            composite_id_hash("tenant!") as u32 & 0xffff_0000,
+            composite_id_hash("tenant!doc2") as u32 & 0xffff_0000
+        );
+
+        let user = string_hash("user!") as u32;
+        assert_eq!(
+            composite_id_hash("tenant!user!doc1") as u32,
+            (tenant & 0xff00_0000) | (user & 0x00ff_0000) | (plain & 0x0000_ffff)
+        );
+    }
+
+    #[test]
+    fn test_leader_urls() {
+        let state = test_state();
+        let leader_for = |id: &str| {
+            if (composite_id_hash(id) as u32) >= 0x8000_0000 {
+                "http://10.0.0.1:8983/solr/films_shard1_replica_n1"
+            } else {
+                "http://10.0.0.2:8983/solr/films_shard2_replica_n2"
+            }
+        };
+
+        for id in &["doc1", "doc2", "tenant!doc1", "other!doc9"] {
+            assert_eq!(leader_url(&state, id, "http").unwrap(), leader_for(id));
+            // base_url, where solr still records it, already holds the scheme
+            assert_eq!(
+                leader_url(&state, id, "https").unwrap(),
+                leader_for(id).replace("http://10.0.0.2", "https://10.0.0.2")
+            );
+        }
+    }
+}
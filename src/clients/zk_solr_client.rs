@@ -2,15 +2,42 @@
 
 use rand::seq::SliceRandom;
 use rand::thread_rng;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
+use std::fs;
 use std::hash::{Hash, Hasher};
+use std::path::Path;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
-use zookeeper::{WatchedEvent, Watcher, ZkResult, ZooKeeper};
+use zookeeper::{Acl, CreateMode, WatchedEvent, Watcher, ZkResult, ZooKeeper};
+
+#[cfg(not(feature = "blocking"))]
+use reqwest::RequestBuilder;
+
+#[cfg(feature = "blocking")]
+use reqwest::blocking::RequestBuilder;
 
 use super::cloud_methods::SolrCloudMethods;
+use super::direct_solr_client::DirectSolrClient;
+use super::routing;
 use crate::config::SolrClientConfig;
-use crate::{SolrCoreMethods, SolrResult};
+use crate::response_types::{SolrClusterState, SolrCollectionState};
+use crate::security::SolrSecurityConfig;
+use crate::{SolrCoreMethods, SolrError, SolrRequestBuilder, SolrResult};
+
+// aliases pointing at other aliases are followed, up to this depth
+const MAX_ALIAS_DEPTH: usize = 8;
+
+// contents of /aliases.json
+#[derive(Deserialize, Default)]
+struct ZkAliases {
+    #[serde(default)]
+    collection: BTreeMap<String, String>,
+    #[serde(default)]
+    collection_metadata: BTreeMap<String, BTreeMap<String, String>>,
+}
 
 // empty logging watcher, for the ZK connection
 struct LoggingWatcher;
@@ -82,6 +109,371 @@ impl ZkSolrClient {
         Ok(children.choose(&mut rng).cloned().unwrap())
     }
 
+    /// Build a DirectSolrClient for each current live node, keyed by node name
+    ///
+    /// Each client shares this client's request config, so per-node requests (eg. health checks
+    /// or system info) can be sent to every node in turn rather than to a random one.
+    pub fn live_node_clients(&self) -> SolrResult<Vec<(String, DirectSolrClient)>> {
+        self.live_nodes()?
+            .into_iter()
+            .map(|node_name| {
+                let mut client = DirectSolrClient::new(&node_name)?;
+                client.set_request_config(self.request_config.clone());
+                Ok((node_name, client))
+            })
+            .collect()
+    }
+
+    /// Read a collection's current state (shards, hash ranges and replicas) from zookeeper
+    ///
+    /// Alias names are resolved to the collection they point to first, as with
+    /// resolve_collection.
+    pub fn collection_state(&self, collection: &str) -> SolrResult<SolrCollectionState> {
+        self.read_collection_state(&self.resolve_collection(collection)?)
+    }
+
+    fn read_collection_state(&self, collection: &str) -> SolrResult<SolrCollectionState> {
+        let path = format!("/collections/{}/state.json", collection);
+        let (data, _) = {
+            let zk = self.zk_connection.lock().unwrap();
+            zk.get_data(&path, false)?
+        };
+
+        let mut states: HashMap<String, SolrCollectionState> = serde_json::from_slice(&data)?;
+        states.remove(collection).ok_or_else(|| {
+            SolrError::RoutingError(format!("no state found for collection {}", collection))
+        })
+    }
+
+    /// Read the state of the whole cluster from zookeeper
+    ///
+    /// This gives the same model as a CLUSTERSTATUS request, without going through a solr node.
+    pub fn cluster_state(&self) -> SolrResult<SolrClusterState> {
+        let live_nodes = self.live_nodes()?;
+        let collection_names = {
+            let zk = self.zk_connection.lock().unwrap();
+            zk.get_children("/collections", false)?
+        };
+
+        let mut collections = BTreeMap::new();
+        for name in collection_names {
+            let state = self.read_collection_state(&name)?;
+            collections.insert(name, state);
+        }
+
+        Ok(SolrClusterState {
+            collections,
+            aliases: self.aliases()?,
+            roles: self.read_optional_json("/roles.json")?.unwrap_or_default(),
+            live_nodes,
+            properties: self.read_optional_json("/clusterprops.json")?,
+        })
+    }
+
+    /// Read all aliases from zookeeper, mapped to the comma separated list of collections they
+    /// point to
+    pub fn aliases(&self) -> SolrResult<BTreeMap<String, String>> {
+        Ok(self.read_aliases()?.collection)
+    }
+
+    /// Read the properties of an alias from zookeeper (eg. the router settings of routed aliases)
+    pub fn alias_properties(&self, alias: &str) -> SolrResult<BTreeMap<String, String>> {
+        Ok(self
+            .read_aliases()?
+            .collection_metadata
+            .remove(alias)
+            .unwrap_or_default())
+    }
+
+    /// Resolve a collection or alias name to the collection it refers to
+    ///
+    /// Names which are not aliases are returned unchanged. An alias for several collections
+    /// resolves to the first of them, which is where solr sends updates for that alias. Routed
+    /// aliases pick a collection per document, so cannot be resolved here; send their updates
+    /// through solr with SolrCloudMethods::update instead.
+    pub fn resolve_collection(&self, name: &str) -> SolrResult<String> {
+        let aliases = self.read_aliases()?;
+
+        let mut resolved = String::from(name);
+        for _ in 0..MAX_ALIAS_DEPTH {
+            let target = match aliases.collection.get(&resolved) {
+                Some(target) => target,
+                None => return Ok(resolved),
+            };
+            let routed = aliases
+                .collection_metadata
+                .get(&resolved)
+                .map(|properties| properties.contains_key("router.name"))
+                .unwrap_or(false);
+            if routed {
+                return Err(SolrError::RoutingError(format!(
+                    "{} is a routed alias, so has no single collection",
+                    resolved
+                )));
+            }
+            resolved = target.split(',').next().unwrap_or_default().to_string();
+        }
+
+        Err(SolrError::RoutingError(format!(
+            "too many levels of aliases for {}",
+            name
+        )))
+    }
+
+    fn read_aliases(&self) -> SolrResult<ZkAliases> {
+        Ok(self
+            .read_optional_json::<ZkAliases>("/aliases.json")?
+            .unwrap_or_default())
+    }
+
+    /// List the files of a configset stored in zookeeper, as paths relative to the configset
+    pub fn list_config_files(&self, configset: &str) -> SolrResult<Vec<String>> {
+        let zk = self.zk_connection.lock().unwrap();
+        let root = format!("/configs/{}", configset);
+
+        let mut files = Vec::new();
+        let mut pending = vec![String::new()];
+        while let Some(relative) = pending.pop() {
+            let children = zk.get_children(&format!("{}{}", root, relative), false)?;
+            if children.is_empty() && !relative.is_empty() {
+                files.push(String::from(&relative[1..]));
+            }
+            for child in children {
+                pending.push(format!("{}/{}", relative, child));
+            }
+        }
+
+        files.sort();
+        Ok(files)
+    }
+
+    /// Read a single file of a configset stored in zookeeper, eg. "managed-schema"
+    pub fn read_config_file(&self, configset: &str, file: &str) -> SolrResult<Vec<u8>> {
+        let zk = self.zk_connection.lock().unwrap();
+        Ok(zk
+            .get_data(&format!("/configs/{}/{}", configset, file), false)?
+            .0)
+    }
+
+    /// Write a single file of a configset stored in zookeeper, creating it if needed
+    ///
+    /// Collections using the configset will only pick up the change once they are reloaded.
+    pub fn write_config_file(&self, configset: &str, file: &str, data: Vec<u8>) -> SolrResult<()> {
+        let path = format!("/configs/{}/{}", configset, file);
+        let zk = self.zk_connection.lock().unwrap();
+
+        if zk.exists(&path, false)?.is_some() {
+            zk.set_data(&path, data, None)?;
+            return Ok(());
+        }
+
+        // create any missing parent znodes first
+        for (separator, _) in path.match_indices('/').skip(1) {
+            let parent = &path[..separator];
+            if zk.exists(parent, false)?.is_none() {
+                zk.create(
+                    parent,
+                    Vec::new(),
+                    Acl::open_unsafe().clone(),
+                    CreateMode::Persistent,
+                )?;
+            }
+        }
+        zk.create(
+            &path,
+            data,
+            Acl::open_unsafe().clone(),
+            CreateMode::Persistent,
+        )?;
+        Ok(())
+    }
+
+    /// Delete a single file of a configset stored in zookeeper
+    pub fn delete_config_file(&self, configset: &str, file: &str) -> SolrResult<()> {
+        let zk = self.zk_connection.lock().unwrap();
+        zk.delete(&format!("/configs/{}/{}", configset, file), None)?;
+        Ok(())
+    }
+
+    /// Download a whole configset from zookeeper into a local directory
+    pub fn download_configset(
+        &self,
+        configset: &str,
+        directory: impl AsRef<Path>,
+    ) -> SolrResult<()> {
+        for file in self.list_config_files(configset)? {
+            let local_path = directory.as_ref().join(&file);
+            if let Some(parent) = local_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(local_path, self.read_config_file(configset, &file)?)?;
+        }
+        Ok(())
+    }
+
+    /// Read security.json from zookeeper, along with its znode version
+    ///
+    /// The config is empty, and the version None, if security hasn't been set up. Pass the version
+    /// on to `write_security_config` to change the config without losing any concurrent edits.
+    pub fn read_security_config(&self) -> SolrResult<(SolrSecurityConfig, Option<i32>)> {
+        let (data, stat) = {
+            let zk = self.zk_connection.lock().unwrap();
+            if zk.exists("/security.json", false)?.is_none() {
+                return Ok((SolrSecurityConfig::default(), None));
+            }
+            zk.get_data("/security.json", false)?
+        };
+
+        let config = if data.is_empty() {
+            SolrSecurityConfig::default()
+        } else {
+            serde_json::from_slice(&data)?
+        };
+        Ok((config, Some(stat.version)))
+    }
+
+    /// Replace security.json in zookeeper, if it is still at the version read
+    ///
+    /// The expected version is the one returned by `read_security_config`, where None creates
+    /// security.json. If it has changed since (or was created meanwhile), this fails with a
+    /// `ZookeeperError` of `ZkError::BadVersion` (or `ZkError::NodeExists`), and the config should
+    /// be read and changed again.
+    ///
+    /// Solr nodes watch security.json, and reload their security plugins once it changes. This
+    /// bypasses the Security API, so BasicAuth credentials must already be hashed; prefer
+    /// `SolrAuthenticationCommands::set_user` for adding users.
+    pub fn write_security_config(
+        &self,
+        config: &SolrSecurityConfig,
+        expected_version: Option<i32>,
+    ) -> SolrResult<()> {
+        let data = serde_json::to_vec_pretty(config)?;
+        let zk = self.zk_connection.lock().unwrap();
+
+        match expected_version {
+            Some(version) => {
+                zk.set_data("/security.json", data, Some(version))?;
+            }
+            None => {
+                zk.create(
+                    "/security.json",
+                    data,
+                    Acl::open_unsafe().clone(),
+                    CreateMode::Persistent,
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Read the urlScheme cluster property (eg. https for TLS clusters), which defaults to http
+    fn url_scheme(&self) -> SolrResult<String> {
+        let properties: Option<BTreeMap<String, serde_json::Value>> =
+            self.read_optional_json("/clusterprops.json")?;
+        Ok(properties
+            .as_ref()
+            .and_then(|properties| properties.get("urlScheme"))
+            .and_then(|scheme| scheme.as_str())
+            .unwrap_or("http")
+            .to_string())
+    }
+
+    /// Read and parse a JSON znode, which may be missing (or empty) in a new cluster
+    fn read_optional_json<T: DeserializeOwned>(&self, path: &str) -> SolrResult<Option<T>> {
+        let data = {
+            let zk = self.zk_connection.lock().unwrap();
+            if zk.exists(path, false)?.is_none() {
+                return Ok(None);
+            }
+            zk.get_data(path, false)?.0
+        };
+
+        if data.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(serde_json::from_slice(&data)?))
+        }
+    }
+
+    /// Build update requests which go straight to the leader of each document's shard
+    ///
+    /// The documents are routed the same way solr would (the compositeId hash of the id field,
+    /// including any `tenant!` or `tenant/bits!` prefix), and split into one request per shard
+    /// leader, saving the extra hop of solr forwarding each document to its leader. The id_field
+    /// should name the collection's uniqueKey; if the collection has a router.field, that field
+    /// is used instead. Standard aliases are resolved to their (first) collection.
+    ///
+    /// ```no_run
+    /// # use tokio::runtime::Runtime;
+    /// use stellr::prelude::*;
+    /// use stellr::response_types::SolrUpdateType;
+    /// use serde_json::json;
+    ///
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let solr_client = stellr::ZkSolrClient::new("localhost:9983", "/")?;
+    /// let documents = vec![json!({"id": "acme!1"}), json!({"id": "globex!2"})];
+    ///
+    /// # let mut rt = Runtime::new().unwrap();
+    /// # rt.block_on(async {
+    /// for request in solr_client.leader_updates("films", "id", &documents)? {
+    ///     request.commit().call::<SolrUpdateType>().await?;
+    /// }
+    /// # Ok::<(), Box<dyn Error>>(())
+    /// # })?;
+    /// # Ok(()) }
+    /// ```
+    pub fn leader_updates<T: Serialize>(
+        &self,
+        collection: &str,
+        id_field: &str,
+        documents: &[T],
+    ) -> SolrResult<Vec<RequestBuilder>> {
+        let state = self.collection_state(collection)?;
+        let url_scheme = self.url_scheme()?;
+
+        let mut batches: BTreeMap<String, Vec<&T>> = BTreeMap::new();
+        for document in documents {
+            let route_key =
+                routing::document_route_key(&state, id_field, &serde_json::to_value(document)?)?;
+            batches
+                .entry(routing::leader_url(&state, &route_key, &url_scheme)?)
+                .or_default()
+                .push(document);
+        }
+
+        let client = self.build_client()?;
+        batches
+            .into_iter()
+            .map(|(leader_url, batch)| {
+                client
+                    .post(&format!("{}/update", leader_url))
+                    .content_type("application/json")
+                    .payload(&batch)
+            })
+            .collect()
+    }
+
+    /// Build an update request for the leader of the shard owning a _route_ value
+    ///
+    /// This suits batches where every document shares a routing prefix (eg. `tenant!`), as well
+    /// as deletes, which cannot be routed by document content. The _route_ parameter is also set
+    /// on the request.
+    pub fn leader_update_for_route(
+        &self,
+        collection: &str,
+        route: &str,
+    ) -> SolrResult<RequestBuilder> {
+        let state = self.collection_state(collection)?;
+        let leader_url = routing::leader_url(&state, route, &self.url_scheme()?)?;
+
+        Ok(self
+            .build_client()?
+            .post(&format!("{}/update", leader_url))
+            .content_type("application/json")
+            .query(&[("_route_", route)]))
+    }
+
     /// A basic method to reset dead zookeeper connections
     pub fn reset_zookeeper(&self) -> ZkResult<()> {
         // TODO: Automate the process of re-estabilishing a zookeeper connection
@@ -0,0 +1,1660 @@
+//! Typed requests for the SolrCloud Collections API (/admin/collections)
+//!
+//! Each action is a struct implementing `SolrCollectionsAction`, which knows its action name,
+//! query parameters and the response type solr sends back. Requests can either be built with
+//! `SolrCloudMethods::collections_admin` (and then sent with `call`), or sent in one step with
+//! `run_collections_action`, which returns the typed response.
+//!
+//! ```no_run
+//! # use tokio::runtime::Runtime;
+//! use stellr::collections_admin::{run_collections_action, SolrCreateCollection};
+//! use stellr::ZkSolrClient;
+//!
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! let solr_client = ZkSolrClient::new("localhost:9983", "/")?;
+//! let create = SolrCreateCollection {
+//!     replication_factor: Some(2),
+//!     config_name: Some(String::from("_default")),
+//!     ..SolrCreateCollection::new("films", 2)
+//! };
+//!
+//! # let mut rt = Runtime::new().unwrap();
+//! let response =
+//! # rt.block_on(async {
+//!     run_collections_action(&solr_client, &create)
+//!     .await
+//! # })
+//!     ?;
+//!
+//! assert!(response.is_success());
+//! # Ok(()) }
+//! ```
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::cmp;
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
+use std::time::{Duration, Instant};
+
+use crate::clients::SolrCloudMethods;
+use crate::errors::{SolrError, SolrResult};
+use crate::requests::SolrRequest;
+use crate::response_types::{
+    SolrAsyncState, SolrBackupType, SolrClusterStatusType, SolrCollectionsAdminType,
+    SolrListAliasesType, SolrListBackupType, SolrListCollectionsType, SolrListSnapshotsType,
+    SolrReplicaType, SolrRequestStatusType,
+};
+
+/// A single Collections API action, along with its parameters
+pub trait SolrCollectionsAction {
+    /// The struct solr's response is deserialised into
+    type Response: DeserializeOwned;
+
+    /// Value of the action parameter, eg. CREATE
+    fn action(&self) -> &'static str;
+
+    /// Parameters for this action, not including the action itself
+    fn query_params(&self) -> Vec<(String, String)>;
+}
+
+/// Send a Collections API action and deserialise the response into its typed response struct
+///
+/// Solr reports most failures (eg. creating a collection which already exists) in the response
+/// body rather than as a transport error, so check the returned response as well.
+#[cfg(not(feature = "blocking"))]
+pub async fn run_collections_action<C, A>(client: &C, action: &A) -> SolrResult<A::Response>
+where
+    C: SolrCloudMethods,
+    A: SolrCollectionsAction,
+{
+    client
+        .collections_admin(action)?
+        .call::<A::Response>()
+        .await
+}
+
+/// Send a Collections API action and deserialise the response into its typed response struct
+///
+/// Solr reports most failures (eg. creating a collection which already exists) in the response
+/// body rather than as a transport error, so check the returned response as well.
+#[cfg(feature = "blocking")]
+pub fn run_collections_action<C, A>(client: &C, action: &A) -> SolrResult<A::Response>
+where
+    C: SolrCloudMethods,
+    A: SolrCollectionsAction,
+{
+    client.collections_admin(action)?.call::<A::Response>()
+}
+
+/// Submit a Collections API action to run asynchronously, under a caller-chosen request id
+///
+/// Long running actions (eg. SPLITSHARD or RESTORE) regularly outlast the request timeout from
+/// SolrClientConfig. Submitted asynchronously, solr returns straight away, and the returned
+/// SolrAsyncOperation can be used to poll REQUESTSTATUS until the action completes or fails.
+///
+/// ```no_run
+/// # use tokio::runtime::Runtime;
+/// use stellr::collections_admin::{submit_collections_action, SolrSplitShard};
+/// use stellr::response_types::SolrAsyncState;
+/// use stellr::ZkSolrClient;
+///
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let solr_client = ZkSolrClient::new("localhost:9983", "/")?;
+/// let split = SolrSplitShard::new("films", "shard1");
+///
+/// # let mut rt = Runtime::new().unwrap();
+/// # rt.block_on(async {
+/// let operation = submit_collections_action(&solr_client, &split, "split-films-1").await?;
+/// let outcome = operation.wait().await?;
+/// operation.delete_status().await?;
+///
+/// assert_eq!(outcome.state, SolrAsyncState::Completed);
+/// # Ok::<(), Box<dyn Error>>(())
+/// # })?;
+/// # Ok(()) }
+/// ```
+///
+/// ## Errors
+///
+/// A SolrError::AsyncRequestError is returned if solr refuses the submission, eg. when the request
+/// id is already in use.
+#[cfg(not(feature = "blocking"))]
+pub async fn submit_collections_action<'a, C, A>(
+    client: &'a C,
+    action: &A,
+    request_id: &str,
+) -> SolrResult<SolrAsyncOperation<'a, C, A>>
+where
+    C: SolrCloudMethods,
+    A: SolrCollectionsAction,
+{
+    let submission = SolrAsyncAction { action, request_id };
+    let response = run_collections_action(client, &submission).await?;
+    SolrAsyncOperation::submitted(client, request_id, &response)
+}
+
+/// Submit a Collections API action to run asynchronously, under a caller-chosen request id
+///
+/// Long running actions (eg. SPLITSHARD or RESTORE) regularly outlast the request timeout from
+/// SolrClientConfig. Submitted asynchronously, solr returns straight away, and the returned
+/// SolrAsyncOperation can be used to poll REQUESTSTATUS until the action completes or fails.
+///
+/// ## Errors
+///
+/// A SolrError::AsyncRequestError is returned if solr refuses the submission, eg. when the request
+/// id is already in use.
+#[cfg(feature = "blocking")]
+pub fn submit_collections_action<'a, C, A>(
+    client: &'a C,
+    action: &A,
+    request_id: &str,
+) -> SolrResult<SolrAsyncOperation<'a, C, A>>
+where
+    C: SolrCloudMethods,
+    A: SolrCollectionsAction,
+{
+    let submission = SolrAsyncAction { action, request_id };
+    let response = run_collections_action(client, &submission)?;
+    SolrAsyncOperation::submitted(client, request_id, &response)
+}
+
+/// Any Collections API action, submitted with async=<request_id>
+///
+/// Solr only acknowledges the submission, so the response is always a SolrCollectionsAdminType.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SolrAsyncAction<'a, A> {
+    pub action: &'a A,
+    pub request_id: &'a str,
+}
+
+impl<'a, A: SolrCollectionsAction> SolrCollectionsAction for SolrAsyncAction<'a, A> {
+    type Response = SolrCollectionsAdminType;
+
+    fn action(&self) -> &'static str {
+        self.action.action()
+    }
+
+    fn query_params(&self) -> Vec<(String, String)> {
+        let mut params = self.action.query_params();
+        params.push((String::from("async"), String::from(self.request_id)));
+        params
+    }
+}
+
+/// Check the progress of an async request (action=REQUESTSTATUS)
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SolrRequestStatus {
+    pub request_id: String,
+}
+
+impl SolrRequestStatus {
+    pub fn new(request_id: &str) -> Self {
+        SolrRequestStatus {
+            request_id: String::from(request_id),
+        }
+    }
+}
+
+impl SolrCollectionsAction for SolrRequestStatus {
+    type Response = SolrRequestStatusType;
+
+    fn action(&self) -> &'static str {
+        "REQUESTSTATUS"
+    }
+
+    fn query_params(&self) -> Vec<(String, String)> {
+        vec![(String::from("requestid"), self.request_id.clone())]
+    }
+}
+
+/// Remove the stored status of async requests (action=DELETESTATUS)
+///
+/// Either a single request id is cleared, or with flush set, every completed or failed request.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct SolrDeleteStatus {
+    pub request_id: Option<String>,
+    pub flush: Option<bool>,
+}
+
+impl SolrDeleteStatus {
+    pub fn new(request_id: &str) -> Self {
+        SolrDeleteStatus {
+            request_id: Some(String::from(request_id)),
+            flush: None,
+        }
+    }
+}
+
+impl SolrCollectionsAction for SolrDeleteStatus {
+    type Response = SolrCollectionsAdminType;
+
+    fn action(&self) -> &'static str {
+        "DELETESTATUS"
+    }
+
+    fn query_params(&self) -> Vec<(String, String)> {
+        ParamList::default()
+            .push_opt("requestid", &self.request_id)
+            .push_opt("flush", &self.flush)
+            .finish()
+    }
+}
+
+/// Final state of an async request, along with the action's own typed response
+#[derive(Clone, Debug, PartialEq)]
+pub struct SolrAsyncOutcome<R> {
+    /// Either Completed or Failed
+    pub state: SolrAsyncState,
+    pub message: Option<String>,
+    pub response: R,
+}
+
+/// Handle for an async Collections API request, as returned by submit_collections_action
+///
+/// REQUESTSTATUS is polled starting at poll_interval, doubling after each poll up to
+/// max_poll_interval. By default wait() keeps polling indefinitely; set timeout to give up
+/// (the action itself carries on running in solr).
+#[derive(Clone, Debug)]
+pub struct SolrAsyncOperation<'a, C, A> {
+    client: &'a C,
+    request_id: String,
+    pub poll_interval: Duration,
+    pub max_poll_interval: Duration,
+    pub timeout: Option<Duration>,
+    action: PhantomData<fn() -> A>,
+}
+
+impl<'a, C, A> SolrAsyncOperation<'a, C, A>
+where
+    C: SolrCloudMethods,
+    A: SolrCollectionsAction,
+{
+    /// Resume tracking a request submitted earlier (eg. by another process)
+    pub fn new(client: &'a C, request_id: &str) -> Self {
+        SolrAsyncOperation {
+            client,
+            request_id: String::from(request_id),
+            poll_interval: Duration::from_secs(1),
+            max_poll_interval: Duration::from_secs(30),
+            timeout: None,
+            action: PhantomData,
+        }
+    }
+
+    fn submitted(
+        client: &'a C,
+        request_id: &str,
+        response: &SolrCollectionsAdminType,
+    ) -> SolrResult<Self> {
+        if response.responseHeader.status != 0 || response.error.is_some() {
+            let reason = response
+                .error
+                .as_ref()
+                .and_then(|error| error.msg.clone())
+                .unwrap_or_else(|| format!("status {}", response.responseHeader.status));
+            return Err(SolrError::AsyncRequestError(format!(
+                "request {} was not accepted: {}",
+                request_id, reason
+            )));
+        }
+        Ok(SolrAsyncOperation::new(client, request_id))
+    }
+
+    /// The id this request was submitted under
+    pub fn request_id(&self) -> &str {
+        &self.request_id
+    }
+
+    /// Turn a REQUESTSTATUS response into the final outcome, if the request has finished
+    fn finished(&self, body: Value) -> SolrResult<Option<SolrAsyncOutcome<A::Response>>> {
+        let status: SolrRequestStatusType = serde_json::from_value(body.clone())?;
+        match status.status.state {
+            SolrAsyncState::Completed | SolrAsyncState::Failed => Ok(Some(SolrAsyncOutcome {
+                state: status.status.state,
+                message: status.status.msg,
+                response: serde_json::from_value(body)?,
+            })),
+            SolrAsyncState::NotFound => Err(SolrError::AsyncRequestError(format!(
+                "request {} was not found",
+                self.request_id
+            ))),
+            _ => Ok(None),
+        }
+    }
+
+    /// Work out how long to sleep before the next poll, or fail if the timeout would be passed
+    fn next_poll(&self, started: Instant, interval: Duration) -> SolrResult<Duration> {
+        if let Some(timeout) = self.timeout {
+            if started.elapsed() + interval > timeout {
+                return Err(SolrError::AsyncRequestError(format!(
+                    "timed out waiting for request {}",
+                    self.request_id
+                )));
+            }
+        }
+        Ok(cmp::min(interval * 2, self.max_poll_interval))
+    }
+}
+
+#[cfg(not(feature = "blocking"))]
+impl<'a, C, A> SolrAsyncOperation<'a, C, A>
+where
+    C: SolrCloudMethods,
+    A: SolrCollectionsAction,
+{
+    /// Poll REQUESTSTATUS once, to report on progress
+    pub async fn status(&self) -> SolrResult<SolrRequestStatusType> {
+        run_collections_action(self.client, &SolrRequestStatus::new(&self.request_id)).await
+    }
+
+    /// Poll REQUESTSTATUS with backoff until the request has completed or failed
+    pub async fn wait(&self) -> SolrResult<SolrAsyncOutcome<A::Response>> {
+        let started = Instant::now();
+        let mut interval = self.poll_interval;
+        loop {
+            let body = self
+                .client
+                .collections_admin(&SolrRequestStatus::new(&self.request_id))?
+                .unstructured_call()
+                .await?;
+            if let Some(outcome) = self.finished(body)? {
+                return Ok(outcome);
+            }
+
+            let next_interval = self.next_poll(started, interval)?;
+            tokio::time::delay_for(interval).await;
+            interval = next_interval;
+        }
+    }
+
+    /// Remove the stored status of this request from solr (DELETESTATUS)
+    pub async fn delete_status(&self) -> SolrResult<SolrCollectionsAdminType> {
+        run_collections_action(self.client, &SolrDeleteStatus::new(&self.request_id)).await
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl<'a, C, A> SolrAsyncOperation<'a, C, A>
+where
+    C: SolrCloudMethods,
+    A: SolrCollectionsAction,
+{
+    /// Poll REQUESTSTATUS once, to report on progress
+    pub fn status(&self) -> SolrResult<SolrRequestStatusType> {
+        run_collections_action(self.client, &SolrRequestStatus::new(&self.request_id))
+    }
+
+    /// Poll REQUESTSTATUS with backoff until the request has completed or failed
+    pub fn wait(&self) -> SolrResult<SolrAsyncOutcome<A::Response>> {
+        let started = Instant::now();
+        let mut interval = self.poll_interval;
+        loop {
+            let body = self
+                .client
+                .collections_admin(&SolrRequestStatus::new(&self.request_id))?
+                .unstructured_call()?;
+            if let Some(outcome) = self.finished(body)? {
+                return Ok(outcome);
+            }
+
+            let next_interval = self.next_poll(started, interval)?;
+            std::thread::sleep(interval);
+            interval = next_interval;
+        }
+    }
+
+    /// Remove the stored status of this request from solr (DELETESTATUS)
+    pub fn delete_status(&self) -> SolrResult<SolrCollectionsAdminType> {
+        run_collections_action(self.client, &SolrDeleteStatus::new(&self.request_id))
+    }
+}
+
+/// Document routers which can be used when creating a collection (router.name)
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum SolrRouterName {
+    /// Hash based routing on the uniqueKey (or router.field), with optional prefixes
+    CompositeId,
+    /// Explicit routing, using the shard names given in the shards parameter
+    Implicit,
+}
+
+impl SolrRouterName {
+    fn as_str(&self) -> &'static str {
+        match self {
+            SolrRouterName::CompositeId => "compositeId",
+            SolrRouterName::Implicit => "implicit",
+        }
+    }
+}
+
+/// Methods used to split a shard's index (splitMethod)
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum SolrSplitMethod {
+    /// Rewrite the index for each sub-shard (slow, but produces compact indexes)
+    Rewrite,
+    /// Hard-link the existing index files and filter them (fast, but uses more disk at first)
+    Link,
+}
+
+impl SolrSplitMethod {
+    fn as_str(&self) -> &'static str {
+        match self {
+            SolrSplitMethod::Rewrite => "rewrite",
+            SolrSplitMethod::Link => "link",
+        }
+    }
+}
+
+fn replica_type_str(replica_type: SolrReplicaType) -> &'static str {
+    match replica_type {
+        SolrReplicaType::NRT => "NRT",
+        SolrReplicaType::TLOG => "TLOG",
+        SolrReplicaType::PULL => "PULL",
+    }
+}
+
+/// Collect query parameters, skipping any options which have not been set
+#[derive(Default)]
+pub(crate) struct ParamList(Vec<(String, String)>);
+
+impl ParamList {
+    pub(crate) fn push(&mut self, name: &str, value: impl ToString) -> &mut Self {
+        self.0.push((String::from(name), value.to_string()));
+        self
+    }
+
+    pub(crate) fn push_opt<T: ToString>(&mut self, name: &str, value: &Option<T>) -> &mut Self {
+        if let Some(value) = value {
+            self.push(name, value.to_string());
+        }
+        self
+    }
+
+    pub(crate) fn push_list(&mut self, name: &str, values: &[String]) -> &mut Self {
+        if !values.is_empty() {
+            self.push(name, values.join(","));
+        }
+        self
+    }
+
+    pub(crate) fn push_properties(&mut self, properties: &BTreeMap<String, String>) -> &mut Self {
+        for (name, value) in properties {
+            self.push(&format!("property.{}", name), value);
+        }
+        self
+    }
+
+    pub(crate) fn finish(&mut self) -> Vec<(String, String)> {
+        std::mem::take(&mut self.0)
+    }
+}
+
+/// Create a new collection (action=CREATE)
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SolrCreateCollection {
+    pub name: String,
+    /// Number of shards, for the compositeId router (numShards)
+    pub num_shards: Option<u32>,
+    /// Shard names, for the implicit router (shards)
+    pub shards: Vec<String>,
+    pub router_name: Option<SolrRouterName>,
+    /// Field to route documents on, instead of the uniqueKey (router.field)
+    pub router_field: Option<String>,
+    /// Number of NRT replicas per shard (replicationFactor)
+    pub replication_factor: Option<u32>,
+    pub nrt_replicas: Option<u32>,
+    pub tlog_replicas: Option<u32>,
+    pub pull_replicas: Option<u32>,
+    /// Configset to use, which must already be in zookeeper (collection.configName)
+    pub config_name: Option<String>,
+    /// Nodes to place replicas on, eg. 10.0.0.1:8983_solr (createNodeSet)
+    pub create_node_set: Vec<String>,
+    /// Core properties (property.<name>)
+    pub properties: BTreeMap<String, String>,
+    pub wait_for_final_state: Option<bool>,
+}
+
+impl SolrCreateCollection {
+    /// Create a compositeId routed collection with a given number of shards
+    pub fn new(name: &str, num_shards: u32) -> Self {
+        SolrCreateCollection {
+            name: String::from(name),
+            num_shards: Some(num_shards),
+            shards: Vec::new(),
+            router_name: None,
+            router_field: None,
+            replication_factor: None,
+            nrt_replicas: None,
+            tlog_replicas: None,
+            pull_replicas: None,
+            config_name: None,
+            create_node_set: Vec::new(),
+            properties: BTreeMap::new(),
+            wait_for_final_state: None,
+        }
+    }
+
+    /// Create an implicitly routed collection with named shards
+    pub fn implicit(name: &str, shards: &[&str]) -> Self {
+        SolrCreateCollection {
+            num_shards: None,
+            shards: shards.iter().map(|s| String::from(*s)).collect(),
+            router_name: Some(SolrRouterName::Implicit),
+            ..SolrCreateCollection::new(name, 0)
+        }
+    }
+}
+
+impl SolrCollectionsAction for SolrCreateCollection {
+    type Response = SolrCollectionsAdminType;
+
+    fn action(&self) -> &'static str {
+        "CREATE"
+    }
+
+    fn query_params(&self) -> Vec<(String, String)> {
+        ParamList::default()
+            .push("name", &self.name)
+            .push_opt("numShards", &self.num_shards)
+            .push_list("shards", &self.shards)
+            .push_opt("router.name", &self.router_name.map(|r| r.as_str()))
+            .push_opt("router.field", &self.router_field)
+            .push_opt("replicationFactor", &self.replication_factor)
+            .push_opt("nrtReplicas", &self.nrt_replicas)
+            .push_opt("tlogReplicas", &self.tlog_replicas)
+            .push_opt("pullReplicas", &self.pull_replicas)
+            .push_opt("collection.configName", &self.config_name)
+            .push_list("createNodeSet", &self.create_node_set)
+            .push_properties(&self.properties)
+            .push_opt("waitForFinalState", &self.wait_for_final_state)
+            .finish()
+    }
+}
+
+/// Delete a collection (action=DELETE)
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SolrDeleteCollection {
+    pub name: String,
+}
+
+impl SolrDeleteCollection {
+    pub fn new(name: &str) -> Self {
+        SolrDeleteCollection {
+            name: String::from(name),
+        }
+    }
+}
+
+impl SolrCollectionsAction for SolrDeleteCollection {
+    type Response = SolrCollectionsAdminType;
+
+    fn action(&self) -> &'static str {
+        "DELETE"
+    }
+
+    fn query_params(&self) -> Vec<(String, String)> {
+        vec![(String::from("name"), self.name.clone())]
+    }
+}
+
+/// Reload all cores of a collection, eg. after a configset change (action=RELOAD)
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SolrReloadCollection {
+    pub name: String,
+}
+
+impl SolrReloadCollection {
+    pub fn new(name: &str) -> Self {
+        SolrReloadCollection {
+            name: String::from(name),
+        }
+    }
+}
+
+impl SolrCollectionsAction for SolrReloadCollection {
+    type Response = SolrCollectionsAdminType;
+
+    fn action(&self) -> &'static str {
+        "RELOAD"
+    }
+
+    fn query_params(&self) -> Vec<(String, String)> {
+        vec![(String::from("name"), self.name.clone())]
+    }
+}
+
+/// Change attributes of an existing collection (action=MODIFYCOLLECTION)
+///
+/// Attributes are set by name (eg. replicationFactor, collection.configName, or
+/// property.<name>). An empty value unsets the attribute.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SolrModifyCollection {
+    pub collection: String,
+    pub attributes: BTreeMap<String, String>,
+}
+
+impl SolrModifyCollection {
+    pub fn new(collection: &str) -> Self {
+        SolrModifyCollection {
+            collection: String::from(collection),
+            attributes: BTreeMap::new(),
+        }
+    }
+
+    /// Add an attribute to change
+    pub fn set(mut self, attribute: &str, value: impl ToString) -> Self {
+        self.attributes
+            .insert(String::from(attribute), value.to_string());
+        self
+    }
+}
+
+impl SolrCollectionsAction for SolrModifyCollection {
+    type Response = SolrCollectionsAdminType;
+
+    fn action(&self) -> &'static str {
+        "MODIFYCOLLECTION"
+    }
+
+    fn query_params(&self) -> Vec<(String, String)> {
+        let mut params = ParamList::default();
+        params.push("collection", &self.collection);
+        for (attribute, value) in &self.attributes {
+            params.push(attribute, value);
+        }
+        params.finish()
+    }
+}
+
+/// List the names of all collections (action=LIST)
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct SolrListCollections;
+
+impl SolrCollectionsAction for SolrListCollections {
+    type Response = SolrListCollectionsType;
+
+    fn action(&self) -> &'static str {
+        "LIST"
+    }
+
+    fn query_params(&self) -> Vec<(String, String)> {
+        Vec::new()
+    }
+}
+
+/// Fetch the state of the cluster, or of a single collection or shard (action=CLUSTERSTATUS)
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct SolrClusterStatus {
+    pub collection: Option<String>,
+    pub shards: Vec<String>,
+    /// Limit the shards to the one owning this route key (_route_)
+    pub route: Option<String>,
+}
+
+impl SolrCollectionsAction for SolrClusterStatus {
+    type Response = SolrClusterStatusType;
+
+    fn action(&self) -> &'static str {
+        "CLUSTERSTATUS"
+    }
+
+    fn query_params(&self) -> Vec<(String, String)> {
+        ParamList::default()
+            .push_opt("collection", &self.collection)
+            .push_list("shard", &self.shards)
+            .push_opt("_route_", &self.route)
+            .finish()
+    }
+}
+
+/// Add a replica to a shard (action=ADDREPLICA)
+///
+/// The shard can be given either by name, or with a route key (_route_) owned by the shard.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SolrAddReplica {
+    pub collection: String,
+    pub shard: Option<String>,
+    pub route: Option<String>,
+    /// Node to create the replica on, eg. 10.0.0.1:8983_solr
+    pub node: Option<String>,
+    pub replica_type: Option<SolrReplicaType>,
+    pub nrt_replicas: Option<u32>,
+    pub tlog_replicas: Option<u32>,
+    pub pull_replicas: Option<u32>,
+    pub instance_dir: Option<String>,
+    pub data_dir: Option<String>,
+    pub create_node_set: Vec<String>,
+    pub properties: BTreeMap<String, String>,
+    pub wait_for_final_state: Option<bool>,
+}
+
+impl SolrAddReplica {
+    pub fn new(collection: &str, shard: &str) -> Self {
+        SolrAddReplica {
+            collection: String::from(collection),
+            shard: Some(String::from(shard)),
+            route: None,
+            node: None,
+            replica_type: None,
+            nrt_replicas: None,
+            tlog_replicas: None,
+            pull_replicas: None,
+            instance_dir: None,
+            data_dir: None,
+            create_node_set: Vec::new(),
+            properties: BTreeMap::new(),
+            wait_for_final_state: None,
+        }
+    }
+}
+
+impl SolrCollectionsAction for SolrAddReplica {
+    type Response = SolrCollectionsAdminType;
+
+    fn action(&self) -> &'static str {
+        "ADDREPLICA"
+    }
+
+    fn query_params(&self) -> Vec<(String, String)> {
+        ParamList::default()
+            .push("collection", &self.collection)
+            .push_opt("shard", &self.shard)
+            .push_opt("_route_", &self.route)
+            .push_opt("node", &self.node)
+            .push_opt("type", &self.replica_type.map(replica_type_str))
+            .push_opt("nrtReplicas", &self.nrt_replicas)
+            .push_opt("tlogReplicas", &self.tlog_replicas)
+            .push_opt("pullReplicas", &self.pull_replicas)
+            .push_opt("instanceDir", &self.instance_dir)
+            .push_opt("dataDir", &self.data_dir)
+            .push_list("createNodeSet", &self.create_node_set)
+            .push_properties(&self.properties)
+            .push_opt("waitForFinalState", &self.wait_for_final_state)
+            .finish()
+    }
+}
+
+/// Delete replicas, either by name or by count (action=DELETEREPLICA)
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SolrDeleteReplica {
+    pub collection: String,
+    pub shard: Option<String>,
+    /// Replica name, eg. core_node3
+    pub replica: Option<String>,
+    /// Number of replicas to remove from the shard, instead of naming one
+    pub count: Option<u32>,
+    pub delete_instance_dir: Option<bool>,
+    pub delete_data_dir: Option<bool>,
+    pub delete_index: Option<bool>,
+    pub only_if_down: Option<bool>,
+}
+
+impl SolrDeleteReplica {
+    pub fn new(collection: &str, shard: &str, replica: &str) -> Self {
+        SolrDeleteReplica {
+            collection: String::from(collection),
+            shard: Some(String::from(shard)),
+            replica: Some(String::from(replica)),
+            count: None,
+            delete_instance_dir: None,
+            delete_data_dir: None,
+            delete_index: None,
+            only_if_down: None,
+        }
+    }
+}
+
+impl SolrCollectionsAction for SolrDeleteReplica {
+    type Response = SolrCollectionsAdminType;
+
+    fn action(&self) -> &'static str {
+        "DELETEREPLICA"
+    }
+
+    fn query_params(&self) -> Vec<(String, String)> {
+        ParamList::default()
+            .push("collection", &self.collection)
+            .push_opt("shard", &self.shard)
+            .push_opt("replica", &self.replica)
+            .push_opt("count", &self.count)
+            .push_opt("deleteInstanceDir", &self.delete_instance_dir)
+            .push_opt("deleteDataDir", &self.delete_data_dir)
+            .push_opt("deleteIndex", &self.delete_index)
+            .push_opt("onlyIfDown", &self.only_if_down)
+            .finish()
+    }
+}
+
+/// Move a replica to another node (action=MOVEREPLICA)
+///
+/// The replica is given either by name, or by shard and source node.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SolrMoveReplica {
+    pub collection: String,
+    pub replica: Option<String>,
+    pub shard: Option<String>,
+    pub source_node: Option<String>,
+    pub target_node: String,
+    pub in_place_move: Option<bool>,
+    /// Seconds to wait for the new replica to become active
+    pub timeout: Option<u32>,
+}
+
+impl SolrMoveReplica {
+    pub fn new(collection: &str, replica: &str, target_node: &str) -> Self {
+        SolrMoveReplica {
+            collection: String::from(collection),
+            replica: Some(String::from(replica)),
+            shard: None,
+            source_node: None,
+            target_node: String::from(target_node),
+            in_place_move: None,
+            timeout: None,
+        }
+    }
+}
+
+impl SolrCollectionsAction for SolrMoveReplica {
+    type Response = SolrCollectionsAdminType;
+
+    fn action(&self) -> &'static str {
+        "MOVEREPLICA"
+    }
+
+    fn query_params(&self) -> Vec<(String, String)> {
+        ParamList::default()
+            .push("collection", &self.collection)
+            .push_opt("replica", &self.replica)
+            .push_opt("shard", &self.shard)
+            .push_opt("sourceNode", &self.source_node)
+            .push("targetNode", &self.target_node)
+            .push_opt("inPlaceMove", &self.in_place_move)
+            .push_opt("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+/// Split a shard into sub-shards (action=SPLITSHARD)
+///
+/// The shard to split can be given by name, or with a split key (eg. `tenant!`) owned by it.
+/// This regularly takes longer than the default request timeout, so is best run asynchronously.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SolrSplitShard {
+    pub collection: String,
+    pub shard: Option<String>,
+    pub split_key: Option<String>,
+    /// Explicit hash ranges for the sub-shards, eg. 0-1f4,1f5-3e8 (ranges)
+    pub ranges: Option<String>,
+    pub num_sub_shards: Option<u32>,
+    pub split_fuzz: Option<String>,
+    pub split_method: Option<SolrSplitMethod>,
+    pub properties: BTreeMap<String, String>,
+    pub wait_for_final_state: Option<bool>,
+}
+
+impl SolrSplitShard {
+    pub fn new(collection: &str, shard: &str) -> Self {
+        SolrSplitShard {
+            collection: String::from(collection),
+            shard: Some(String::from(shard)),
+            split_key: None,
+            ranges: None,
+            num_sub_shards: None,
+            split_fuzz: None,
+            split_method: None,
+            properties: BTreeMap::new(),
+            wait_for_final_state: None,
+        }
+    }
+}
+
+impl SolrCollectionsAction for SolrSplitShard {
+    type Response = SolrCollectionsAdminType;
+
+    fn action(&self) -> &'static str {
+        "SPLITSHARD"
+    }
+
+    fn query_params(&self) -> Vec<(String, String)> {
+        ParamList::default()
+            .push("collection", &self.collection)
+            .push_opt("shard", &self.shard)
+            .push_opt("split.key", &self.split_key)
+            .push_opt("ranges", &self.ranges)
+            .push_opt("numSubShards", &self.num_sub_shards)
+            .push_opt("splitFuzz", &self.split_fuzz)
+            .push_opt("splitMethod", &self.split_method.map(|m| m.as_str()))
+            .push_properties(&self.properties)
+            .push_opt("waitForFinalState", &self.wait_for_final_state)
+            .finish()
+    }
+}
+
+/// Add a shard to an implicitly routed collection (action=CREATESHARD)
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SolrCreateShard {
+    pub collection: String,
+    pub shard: String,
+    pub create_node_set: Vec<String>,
+    pub properties: BTreeMap<String, String>,
+    pub wait_for_final_state: Option<bool>,
+}
+
+impl SolrCreateShard {
+    pub fn new(collection: &str, shard: &str) -> Self {
+        SolrCreateShard {
+            collection: String::from(collection),
+            shard: String::from(shard),
+            create_node_set: Vec::new(),
+            properties: BTreeMap::new(),
+            wait_for_final_state: None,
+        }
+    }
+}
+
+impl SolrCollectionsAction for SolrCreateShard {
+    type Response = SolrCollectionsAdminType;
+
+    fn action(&self) -> &'static str {
+        "CREATESHARD"
+    }
+
+    fn query_params(&self) -> Vec<(String, String)> {
+        ParamList::default()
+            .push("collection", &self.collection)
+            .push("shard", &self.shard)
+            .push_list("createNodeSet", &self.create_node_set)
+            .push_properties(&self.properties)
+            .push_opt("waitForFinalState", &self.wait_for_final_state)
+            .finish()
+    }
+}
+
+/// Delete an inactive shard, or any shard of an implicitly routed collection (action=DELETESHARD)
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SolrDeleteShard {
+    pub collection: String,
+    pub shard: String,
+    pub delete_instance_dir: Option<bool>,
+    pub delete_data_dir: Option<bool>,
+    pub delete_index: Option<bool>,
+}
+
+impl SolrDeleteShard {
+    pub fn new(collection: &str, shard: &str) -> Self {
+        SolrDeleteShard {
+            collection: String::from(collection),
+            shard: String::from(shard),
+            delete_instance_dir: None,
+            delete_data_dir: None,
+            delete_index: None,
+        }
+    }
+}
+
+impl SolrCollectionsAction for SolrDeleteShard {
+    type Response = SolrCollectionsAdminType;
+
+    fn action(&self) -> &'static str {
+        "DELETESHARD"
+    }
+
+    fn query_params(&self) -> Vec<(String, String)> {
+        ParamList::default()
+            .push("collection", &self.collection)
+            .push("shard", &self.shard)
+            .push_opt("deleteInstanceDir", &self.delete_instance_dir)
+            .push_opt("deleteDataDir", &self.delete_data_dir)
+            .push_opt("deleteIndex", &self.delete_index)
+            .finish()
+    }
+}
+
+/// Routing settings for a time routed alias
+///
+/// Solr creates a new collection for each interval (eg. +1DAY) starting from start, and routes
+/// each document by the date in field.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SolrTimeRouter {
+    pub field: String,
+    /// Date of the first collection, eg. NOW/DAY or 2021-01-01T00:00:00Z (router.start)
+    pub start: String,
+    /// Date math for the span of each collection, eg. +1DAY (router.interval)
+    pub interval: String,
+    /// How far into the future documents are accepted (router.maxFutureMs)
+    pub max_future_ms: Option<u64>,
+    /// Date math for creating the next collection ahead of time, eg. 90MINUTES
+    /// (router.preemptiveCreateMath)
+    pub preemptive_create_math: Option<String>,
+    /// Date math for deleting old collections, eg. /DAY-30DAYS (router.autoDeleteAge)
+    pub auto_delete_age: Option<String>,
+    /// Time zone used for rounding dates (TZ)
+    pub time_zone: Option<String>,
+}
+
+/// Routing settings for a category routed alias
+///
+/// Solr creates a new collection for each distinct value of field.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SolrCategoryRouter {
+    pub field: String,
+    /// Maximum number of collections to create (router.maxCardinality)
+    pub max_cardinality: Option<u32>,
+    /// Regular expression category values must match (router.mustMatch)
+    pub must_match: Option<String>,
+}
+
+/// Routers for routed aliases
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum SolrAliasRouter {
+    Time(SolrTimeRouter),
+    Category(SolrCategoryRouter),
+}
+
+impl SolrAliasRouter {
+    fn push_params(&self, params: &mut ParamList) {
+        match self {
+            SolrAliasRouter::Time(router) => {
+                params
+                    .push("router.name", "time")
+                    .push("router.field", &router.field)
+                    .push("router.start", &router.start)
+                    .push("router.interval", &router.interval)
+                    .push_opt("router.maxFutureMs", &router.max_future_ms)
+                    .push_opt(
+                        "router.preemptiveCreateMath",
+                        &router.preemptive_create_math,
+                    )
+                    .push_opt("router.autoDeleteAge", &router.auto_delete_age)
+                    .push_opt("TZ", &router.time_zone);
+            }
+            SolrAliasRouter::Category(router) => {
+                params
+                    .push("router.name", "category")
+                    .push("router.field", &router.field)
+                    .push_opt("router.maxCardinality", &router.max_cardinality)
+                    .push_opt("router.mustMatch", &router.must_match);
+            }
+        }
+    }
+}
+
+/// Create (or repoint) an alias (action=CREATEALIAS)
+///
+/// A standard alias points at a list of existing collections; repointing it at a new collection
+/// is how blue/green reindexing is done. A routed alias instead creates its own collections, using
+/// collection_template for their settings (its name is ignored).
+///
+/// ```
+/// use stellr::collections_admin::{
+///     SolrAliasRouter, SolrCreateAlias, SolrCreateCollection, SolrTimeRouter,
+/// };
+///
+/// let films_alias = SolrCreateAlias::new("films", &["films_v2"]);
+///
+/// let logs_alias = SolrCreateAlias::routed(
+///     "logs",
+///     SolrAliasRouter::Time(SolrTimeRouter {
+///         field: String::from("timestamp_dt"),
+///         start: String::from("NOW/DAY"),
+///         interval: String::from("+1DAY"),
+///         max_future_ms: None,
+///         preemptive_create_math: None,
+///         auto_delete_age: Some(String::from("/DAY-30DAYS")),
+///         time_zone: None,
+///     }),
+///     SolrCreateCollection {
+///         config_name: Some(String::from("logs_config")),
+///         ..SolrCreateCollection::new("", 1)
+///     },
+/// );
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SolrCreateAlias {
+    pub name: String,
+    pub collections: Vec<String>,
+    pub router: Option<SolrAliasRouter>,
+    pub collection_template: Option<SolrCreateCollection>,
+}
+
+impl SolrCreateAlias {
+    /// Create a standard alias for a list of collections
+    pub fn new(name: &str, collections: &[&str]) -> Self {
+        SolrCreateAlias {
+            name: String::from(name),
+            collections: collections.iter().map(|c| String::from(*c)).collect(),
+            router: None,
+            collection_template: None,
+        }
+    }
+
+    /// Create a routed alias, whose collections are created using the template's settings
+    pub fn routed(
+        name: &str,
+        router: SolrAliasRouter,
+        collection_template: SolrCreateCollection,
+    ) -> Self {
+        SolrCreateAlias {
+            name: String::from(name),
+            collections: Vec::new(),
+            router: Some(router),
+            collection_template: Some(collection_template),
+        }
+    }
+}
+
+impl SolrCollectionsAction for SolrCreateAlias {
+    type Response = SolrCollectionsAdminType;
+
+    fn action(&self) -> &'static str {
+        "CREATEALIAS"
+    }
+
+    fn query_params(&self) -> Vec<(String, String)> {
+        let mut params = ParamList::default();
+        params
+            .push("name", &self.name)
+            .push_list("collections", &self.collections);
+        if let Some(router) = &self.router {
+            router.push_params(&mut params);
+        }
+        if let Some(template) = &self.collection_template {
+            for (name, value) in template.query_params() {
+                if name != "name" {
+                    params.push(&format!("create-collection.{}", name), value);
+                }
+            }
+        }
+        params.finish()
+    }
+}
+
+/// Delete an alias, leaving its collections in place (action=DELETEALIAS)
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SolrDeleteAlias {
+    pub name: String,
+}
+
+impl SolrDeleteAlias {
+    pub fn new(name: &str) -> Self {
+        SolrDeleteAlias {
+            name: String::from(name),
+        }
+    }
+}
+
+impl SolrCollectionsAction for SolrDeleteAlias {
+    type Response = SolrCollectionsAdminType;
+
+    fn action(&self) -> &'static str {
+        "DELETEALIAS"
+    }
+
+    fn query_params(&self) -> Vec<(String, String)> {
+        vec![(String::from("name"), self.name.clone())]
+    }
+}
+
+/// List all aliases, along with their properties (action=LISTALIASES)
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct SolrListAliases;
+
+impl SolrCollectionsAction for SolrListAliases {
+    type Response = SolrListAliasesType;
+
+    fn action(&self) -> &'static str {
+        "LISTALIASES"
+    }
+
+    fn query_params(&self) -> Vec<(String, String)> {
+        Vec::new()
+    }
+}
+
+/// Set or remove properties on an alias (action=ALIASPROP)
+///
+/// An empty value removes the property.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SolrAliasProperties {
+    pub name: String,
+    pub properties: BTreeMap<String, String>,
+}
+
+impl SolrAliasProperties {
+    pub fn new(name: &str) -> Self {
+        SolrAliasProperties {
+            name: String::from(name),
+            properties: BTreeMap::new(),
+        }
+    }
+
+    /// Add a property to set
+    pub fn set(mut self, property: &str, value: impl ToString) -> Self {
+        self.properties
+            .insert(String::from(property), value.to_string());
+        self
+    }
+}
+
+impl SolrCollectionsAction for SolrAliasProperties {
+    type Response = SolrCollectionsAdminType;
+
+    fn action(&self) -> &'static str {
+        "ALIASPROP"
+    }
+
+    fn query_params(&self) -> Vec<(String, String)> {
+        ParamList::default()
+            .push("name", &self.name)
+            .push_properties(&self.properties)
+            .finish()
+    }
+}
+
+/// Where a backup is stored: a named backup, under a location within a backup repository
+///
+/// The repository is one of the backup repositories configured in solr.xml, or solr's default
+/// (local filesystem) repository when left unset. Location must be visible to every node, eg. a
+/// shared mount for the local filesystem repository.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SolrBackupLocation {
+    pub name: String,
+    pub location: Option<String>,
+    pub repository: Option<String>,
+}
+
+impl SolrBackupLocation {
+    pub fn new(name: &str, location: &str) -> Self {
+        SolrBackupLocation {
+            name: String::from(name),
+            location: Some(String::from(location)),
+            repository: None,
+        }
+    }
+
+    fn push_params(&self, params: &mut ParamList) {
+        params
+            .push("name", &self.name)
+            .push_opt("location", &self.location)
+            .push_opt("repository", &self.repository);
+    }
+}
+
+/// Back up a collection (action=BACKUP)
+///
+/// Incremental backups add a new backup point to an existing backup, sharing unchanged index
+/// files, and can prune old backup points with max_num_backup_points. Backups of large
+/// collections should be run with submit_collections_action.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SolrBackup {
+    pub collection: String,
+    pub backup: SolrBackupLocation,
+    pub incremental: Option<bool>,
+    pub max_num_backup_points: Option<u32>,
+    /// Back up a named snapshot rather than the latest commit (commitName)
+    pub commit_name: Option<String>,
+}
+
+impl SolrBackup {
+    pub fn new(collection: &str, backup: SolrBackupLocation) -> Self {
+        SolrBackup {
+            collection: String::from(collection),
+            backup,
+            incremental: None,
+            max_num_backup_points: None,
+            commit_name: None,
+        }
+    }
+}
+
+impl SolrCollectionsAction for SolrBackup {
+    type Response = SolrBackupType;
+
+    fn action(&self) -> &'static str {
+        "BACKUP"
+    }
+
+    fn query_params(&self) -> Vec<(String, String)> {
+        let mut params = ParamList::default();
+        params.push("collection", &self.collection);
+        self.backup.push_params(&mut params);
+        params
+            .push_opt("incremental", &self.incremental)
+            .push_opt("maxNumBackupPoints", &self.max_num_backup_points)
+            .push_opt("commitName", &self.commit_name)
+            .finish()
+    }
+}
+
+/// Restore a backup into a new collection (action=RESTORE)
+///
+/// Without a backup_id, the most recent backup point is restored. Any collection settings left
+/// unset are taken from the backed up collection.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SolrRestore {
+    /// Name of the collection to create
+    pub collection: String,
+    pub backup: SolrBackupLocation,
+    pub backup_id: Option<u32>,
+    pub config_name: Option<String>,
+    pub replication_factor: Option<u32>,
+    pub nrt_replicas: Option<u32>,
+    pub tlog_replicas: Option<u32>,
+    pub pull_replicas: Option<u32>,
+    pub create_node_set: Vec<String>,
+    pub properties: BTreeMap<String, String>,
+}
+
+impl SolrRestore {
+    pub fn new(collection: &str, backup: SolrBackupLocation) -> Self {
+        SolrRestore {
+            collection: String::from(collection),
+            backup,
+            backup_id: None,
+            config_name: None,
+            replication_factor: None,
+            nrt_replicas: None,
+            tlog_replicas: None,
+            pull_replicas: None,
+            create_node_set: Vec::new(),
+            properties: BTreeMap::new(),
+        }
+    }
+}
+
+impl SolrCollectionsAction for SolrRestore {
+    type Response = SolrCollectionsAdminType;
+
+    fn action(&self) -> &'static str {
+        "RESTORE"
+    }
+
+    fn query_params(&self) -> Vec<(String, String)> {
+        let mut params = ParamList::default();
+        params.push("collection", &self.collection);
+        self.backup.push_params(&mut params);
+        params
+            .push_opt("backupId", &self.backup_id)
+            .push_opt("collection.configName", &self.config_name)
+            .push_opt("replicationFactor", &self.replication_factor)
+            .push_opt("nrtReplicas", &self.nrt_replicas)
+            .push_opt("tlogReplicas", &self.tlog_replicas)
+            .push_opt("pullReplicas", &self.pull_replicas)
+            .push_list("createNodeSet", &self.create_node_set)
+            .push_properties(&self.properties)
+            .finish()
+    }
+}
+
+/// List the backup points of an incremental backup (action=LISTBACKUP)
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SolrListBackup {
+    pub backup: SolrBackupLocation,
+}
+
+impl SolrListBackup {
+    pub fn new(backup: SolrBackupLocation) -> Self {
+        SolrListBackup { backup }
+    }
+}
+
+impl SolrCollectionsAction for SolrListBackup {
+    type Response = SolrListBackupType;
+
+    fn action(&self) -> &'static str {
+        "LISTBACKUP"
+    }
+
+    fn query_params(&self) -> Vec<(String, String)> {
+        let mut params = ParamList::default();
+        self.backup.push_params(&mut params);
+        params.finish()
+    }
+}
+
+/// Delete backup points from an incremental backup (action=DELETEBACKUP)
+///
+/// Set one of backup_id (a single backup point), max_num_backup_points (keep only the most
+/// recent points) or purge_unused (remove index files no longer used by any point).
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SolrDeleteBackup {
+    pub backup: SolrBackupLocation,
+    pub backup_id: Option<u32>,
+    pub max_num_backup_points: Option<u32>,
+    pub purge_unused: Option<bool>,
+}
+
+impl SolrDeleteBackup {
+    pub fn new(backup: SolrBackupLocation) -> Self {
+        SolrDeleteBackup {
+            backup,
+            backup_id: None,
+            max_num_backup_points: None,
+            purge_unused: None,
+        }
+    }
+}
+
+impl SolrCollectionsAction for SolrDeleteBackup {
+    type Response = SolrCollectionsAdminType;
+
+    fn action(&self) -> &'static str {
+        "DELETEBACKUP"
+    }
+
+    fn query_params(&self) -> Vec<(String, String)> {
+        let mut params = ParamList::default();
+        self.backup.push_params(&mut params);
+        params
+            .push_opt("backupId", &self.backup_id)
+            .push_opt("maxNumBackupPoints", &self.max_num_backup_points)
+            .push_opt("purgeUnused", &self.purge_unused)
+            .finish()
+    }
+}
+
+/// Create a named snapshot of a collection's current commit (action=CREATESNAPSHOT)
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SolrCreateSnapshot {
+    pub collection: String,
+    pub commit_name: String,
+}
+
+impl SolrCreateSnapshot {
+    pub fn new(collection: &str, commit_name: &str) -> Self {
+        SolrCreateSnapshot {
+            collection: String::from(collection),
+            commit_name: String::from(commit_name),
+        }
+    }
+}
+
+impl SolrCollectionsAction for SolrCreateSnapshot {
+    type Response = SolrCollectionsAdminType;
+
+    fn action(&self) -> &'static str {
+        "CREATESNAPSHOT"
+    }
+
+    fn query_params(&self) -> Vec<(String, String)> {
+        vec![
+            (String::from("collection"), self.collection.clone()),
+            (String::from("commitName"), self.commit_name.clone()),
+        ]
+    }
+}
+
+/// List the snapshots of a collection (action=LISTSNAPSHOTS)
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SolrListSnapshots {
+    pub collection: String,
+}
+
+impl SolrListSnapshots {
+    pub fn new(collection: &str) -> Self {
+        SolrListSnapshots {
+            collection: String::from(collection),
+        }
+    }
+}
+
+impl SolrCollectionsAction for SolrListSnapshots {
+    type Response = SolrListSnapshotsType;
+
+    fn action(&self) -> &'static str {
+        "LISTSNAPSHOTS"
+    }
+
+    fn query_params(&self) -> Vec<(String, String)> {
+        vec![(String::from("collection"), self.collection.clone())]
+    }
+}
+
+/// Delete a named snapshot, releasing its index files (action=DELETESNAPSHOT)
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SolrDeleteSnapshot {
+    pub collection: String,
+    pub commit_name: String,
+}
+
+impl SolrDeleteSnapshot {
+    pub fn new(collection: &str, commit_name: &str) -> Self {
+        SolrDeleteSnapshot {
+            collection: String::from(collection),
+            commit_name: String::from(commit_name),
+        }
+    }
+}
+
+impl SolrCollectionsAction for SolrDeleteSnapshot {
+    type Response = SolrCollectionsAdminType;
+
+    fn action(&self) -> &'static str {
+        "DELETESNAPSHOT"
+    }
+
+    fn query_params(&self) -> Vec<(String, String)> {
+        vec![
+            (String::from("collection"), self.collection.clone()),
+            (String::from("commitName"), self.commit_name.clone()),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_create_collection_params() {
+        let mut create = SolrCreateCollection {
+            replication_factor: Some(2),
+            pull_replicas: Some(1),
+            config_name: Some(String::from("films_config")),
+            create_node_set: vec![String::from("a:8983_solr"), String::from("b:8983_solr")],
+            ..SolrCreateCollection::new("films", 3)
+        };
+        create
+            .properties
+            .insert(String::from("ulogDir"), String::from("/tmp"));
+
+        let expected: Vec<(String, String)> = vec![
+            ("name", "films"),
+            ("numShards", "3"),
+            ("replicationFactor", "2"),
+            ("pullReplicas", "1"),
+            ("collection.configName", "films_config"),
+            ("createNodeSet", "a:8983_solr,b:8983_solr"),
+            ("property.ulogDir", "/tmp"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (String::from(k), String::from(v)))
+        .collect();
+        assert_eq!(create.action(), "CREATE");
+        assert_eq!(create.query_params(), expected);
+
+        let implicit = SolrCreateCollection::implicit("logs", &["2020", "2021"]);
+        assert_eq!(
+            implicit.query_params(),
+            vec![
+                (String::from("name"), String::from("logs")),
+                (String::from("shards"), String::from("2020,2021")),
+                (String::from("router.name"), String::from("implicit")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_replica_params() {
+        let add = SolrAddReplica {
+            replica_type: Some(SolrReplicaType::TLOG),
+            ..SolrAddReplica::new("films", "shard1")
+        };
+        assert_eq!(
+            add.query_params(),
+            vec![
+                (String::from("collection"), String::from("films")),
+                (String::from("shard"), String::from("shard1")),
+                (String::from("type"), String::from("TLOG")),
+            ]
+        );
+
+        let modify = SolrModifyCollection::new("films").set("replicationFactor", 3);
+        assert_eq!(
+            modify.query_params(),
+            vec![
+                (String::from("collection"), String::from("films")),
+                (String::from("replicationFactor"), String::from("3")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_routed_alias_params() {
+        let category = SolrCreateAlias::routed(
+            "products",
+            SolrAliasRouter::Category(SolrCategoryRouter {
+                field: String::from("brand_s"),
+                max_cardinality: Some(20),
+                must_match: None,
+            }),
+            SolrCreateCollection {
+                config_name: Some(String::from("products_config")),
+                ..SolrCreateCollection::new("ignored", 2)
+            },
+        );
+
+        assert_eq!(
+            category.query_params(),
+            vec![
+                (String::from("name"), String::from("products")),
+                (String::from("router.name"), String::from("category")),
+                (String::from("router.field"), String::from("brand_s")),
+                (String::from("router.maxCardinality"), String::from("20")),
+                (
+                    String::from("create-collection.numShards"),
+                    String::from("2")
+                ),
+                (
+                    String::from("create-collection.collection.configName"),
+                    String::from("products_config")
+                ),
+            ]
+        );
+
+        let standard = SolrCreateAlias::new("films", &["films_v1", "films_v2"]);
+        assert_eq!(
+            standard.query_params(),
+            vec![
+                (String::from("name"), String::from("films")),
+                (
+                    String::from("collections"),
+                    String::from("films_v1,films_v2")
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,280 @@
+//! Config API and Request Parameters API commands
+//!
+//! Solr's Config API (/config) edits a core's configuration through an overlay (configoverlay.json)
+//! kept alongside solrconfig.xml, and the Request Parameters API (/config/params) manages named
+//! param sets, which requests apply with `SolrRequestBuilder::use_params`. The commands here are
+//! sent with `SolrCoreMethods::update_config` and `SolrCoreMethods::update_params`.
+//!
+//! ```no_run
+//! # use tokio::runtime::Runtime;
+//! use stellr::config_api::{SolrConfigCommands, SolrConfigComponent, SolrParamSetChanges};
+//! use stellr::prelude::*;
+//! use stellr::response_types::{SolrSelectType, SolrUpdateType};
+//! use serde_json::json;
+//!
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! let commands = SolrConfigCommands::new()
+//!     .set_property("query.filterCache.size", 1024)
+//!     .add(
+//!         SolrConfigComponent::RequestHandler,
+//!         json!({"name": "/films", "class": "solr.SearchHandler", "defaults": {"rows": 20}}),
+//!     );
+//! let param_sets = SolrParamSetChanges::new()
+//!     .set("genre_facets", json!({"facet": "true", "facet.field": "genre"}));
+//!
+//! let solr_client = stellr::DirectSolrClient::new("http://localhost:8983/solr")?;
+//!
+//! # let mut rt = Runtime::new().unwrap();
+//! # rt.block_on(async {
+//! solr_client
+//!     .update_config("films", &commands)?
+//!     .call::<SolrUpdateType>()
+//!     .await?;
+//! solr_client
+//!     .update_params("films", &param_sets)?
+//!     .call::<SolrUpdateType>()
+//!     .await?;
+//!
+//! let result_struct = solr_client
+//!     .select("films")?
+//!     .q("*:*")
+//!     .use_params("genre_facets")
+//!     .call::<SolrSelectType<serde_json::Value>>()
+//!     .await?;
+//! # Ok::<(), stellr::SolrError>(())
+//! # })?;
+//! # Ok(()) }
+//! ```
+
+use serde::ser::{SerializeMap, Serializer};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::fmt;
+
+use crate::requests::update_commands::SolrCommandList;
+
+/// The kinds of solrconfig.xml component the Config API can add, update or delete
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum SolrConfigComponent {
+    RequestHandler,
+    SearchComponent,
+    InitParams,
+    QueryResponseWriter,
+    QueryParser,
+    ValueSourceParser,
+    Transformer,
+    UpdateProcessor,
+    QueryConverter,
+    Listener,
+    RuntimeLib,
+}
+
+impl SolrConfigComponent {
+    fn add_command(self) -> &'static str {
+        match self {
+            SolrConfigComponent::RequestHandler => "add-requesthandler",
+            SolrConfigComponent::SearchComponent => "add-searchcomponent",
+            SolrConfigComponent::InitParams => "add-initparams",
+            SolrConfigComponent::QueryResponseWriter => "add-queryresponsewriter",
+            SolrConfigComponent::QueryParser => "add-queryparser",
+            SolrConfigComponent::ValueSourceParser => "add-valuesourceparser",
+            SolrConfigComponent::Transformer => "add-transformer",
+            SolrConfigComponent::UpdateProcessor => "add-updateprocessor",
+            SolrConfigComponent::QueryConverter => "add-queryconverter",
+            SolrConfigComponent::Listener => "add-listener",
+            SolrConfigComponent::RuntimeLib => "add-runtimelib",
+        }
+    }
+
+    fn update_command(self) -> &'static str {
+        match self {
+            SolrConfigComponent::RequestHandler => "update-requesthandler",
+            SolrConfigComponent::SearchComponent => "update-searchcomponent",
+            SolrConfigComponent::InitParams => "update-initparams",
+            SolrConfigComponent::QueryResponseWriter => "update-queryresponsewriter",
+            SolrConfigComponent::QueryParser => "update-queryparser",
+            SolrConfigComponent::ValueSourceParser => "update-valuesourceparser",
+            SolrConfigComponent::Transformer => "update-transformer",
+            SolrConfigComponent::UpdateProcessor => "update-updateprocessor",
+            SolrConfigComponent::QueryConverter => "update-queryconverter",
+            SolrConfigComponent::Listener => "update-listener",
+            SolrConfigComponent::RuntimeLib => "update-runtimelib",
+        }
+    }
+
+    fn delete_command(self) -> &'static str {
+        match self {
+            SolrConfigComponent::RequestHandler => "delete-requesthandler",
+            SolrConfigComponent::SearchComponent => "delete-searchcomponent",
+            SolrConfigComponent::InitParams => "delete-initparams",
+            SolrConfigComponent::QueryResponseWriter => "delete-queryresponsewriter",
+            SolrConfigComponent::QueryParser => "delete-queryparser",
+            SolrConfigComponent::ValueSourceParser => "delete-valuesourceparser",
+            SolrConfigComponent::Transformer => "delete-transformer",
+            SolrConfigComponent::UpdateProcessor => "delete-updateprocessor",
+            SolrConfigComponent::QueryConverter => "delete-queryconverter",
+            SolrConfigComponent::Listener => "delete-listener",
+            SolrConfigComponent::RuntimeLib => "delete-runtimelib",
+        }
+    }
+}
+
+/// Builder for a Config API request holding several commands
+///
+/// Like the JSON update handler, /config accepts a single object with repeated command keys,
+/// which are applied in order.
+#[derive(Serialize, Clone, Debug, Default, PartialEq)]
+#[serde(transparent)]
+pub struct SolrConfigCommands {
+    commands: SolrCommandList,
+}
+
+impl SolrConfigCommands {
+    pub fn new() -> SolrConfigCommands {
+        SolrConfigCommands::default()
+    }
+
+    /// Override one of the common solrconfig.xml properties, eg. updateHandler.autoCommit.maxTime
+    pub fn set_property(self, name: &str, value: impl Into<Value>) -> SolrConfigCommands {
+        self.push("set-property", json!({ name: value.into() }))
+    }
+
+    /// Remove a property override, reverting to the value from solrconfig.xml
+    pub fn unset_property(self, name: &str) -> SolrConfigCommands {
+        self.push("unset-property", json!(name))
+    }
+
+    /// Set a user property, for use as a ${name} substitution within solrconfig.xml
+    pub fn set_user_property(self, name: &str, value: impl Into<Value>) -> SolrConfigCommands {
+        self.push("set-user-property", json!({ name: value.into() }))
+    }
+
+    pub fn unset_user_property(self, name: &str) -> SolrConfigCommands {
+        self.push("unset-user-property", json!(name))
+    }
+
+    /// Add a component, defined in the same shape as its solrconfig.xml element
+    ///
+    /// The definition needs a name (eg. the request handler's path) and, for most components, a
+    /// class.
+    pub fn add(self, component: SolrConfigComponent, definition: Value) -> SolrConfigCommands {
+        self.push(component.add_command(), definition)
+    }
+
+    /// Replace the definition of an existing component, matched by name
+    pub fn update(self, component: SolrConfigComponent, definition: Value) -> SolrConfigCommands {
+        self.push(component.update_command(), definition)
+    }
+
+    pub fn delete(self, component: SolrConfigComponent, name: &str) -> SolrConfigCommands {
+        self.push(component.delete_command(), json!(name))
+    }
+
+    fn push(mut self, command: &'static str, value: Value) -> SolrConfigCommands {
+        self.commands.push(command, value);
+        self
+    }
+}
+
+impl fmt::Display for SolrConfigCommands {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.commands.fmt(f)
+    }
+}
+
+/// Builder for a Request Parameters API request, creating, changing or deleting param sets
+///
+/// Param set values are request parameters, eg. `{"facet": "true", "facet.field": ["genre",
+/// "directed_by"]}`. Setting a param set replaces it entirely, while updating one only changes
+/// the parameters given.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SolrParamSetChanges {
+    set: Map<String, Value>,
+    update: Map<String, Value>,
+    delete: Vec<String>,
+}
+
+impl SolrParamSetChanges {
+    pub fn new() -> SolrParamSetChanges {
+        SolrParamSetChanges::default()
+    }
+
+    /// Create or replace a param set
+    pub fn set(mut self, name: &str, params: Value) -> SolrParamSetChanges {
+        self.set.insert(String::from(name), params);
+        self
+    }
+
+    /// Merge parameters into an existing param set
+    pub fn update(mut self, name: &str, params: Value) -> SolrParamSetChanges {
+        self.update.insert(String::from(name), params);
+        self
+    }
+
+    pub fn delete(mut self, name: &str) -> SolrParamSetChanges {
+        self.delete.push(String::from(name));
+        self
+    }
+}
+
+impl Serialize for SolrParamSetChanges {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        if !self.set.is_empty() {
+            map.serialize_entry("set", &self.set)?;
+        }
+        if !self.update.is_empty() {
+            map.serialize_entry("update", &self.update)?;
+        }
+        if !self.delete.is_empty() {
+            map.serialize_entry("delete", &self.delete)?;
+        }
+        map.end()
+    }
+}
+
+impl fmt::Display for SolrParamSetChanges {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match serde_json::to_string(self) {
+            Ok(body) => write!(f, "{}", body),
+            Err(_) => Err(fmt::Error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_config_commands_body() {
+        let commands = SolrConfigCommands::new()
+            .set_property("updateHandler.autoCommit.maxTime", 15000)
+            .update(
+                SolrConfigComponent::SearchComponent,
+                json!({"name": "spellcheck", "class": "solr.SpellCheckComponent"}),
+            )
+            .delete(SolrConfigComponent::RequestHandler, "/old")
+            .set_property("query.filterCache.size", 1024);
+
+        assert_eq!(
+            commands.to_string(),
+            r#"{"set-property":{"updateHandler.autoCommit.maxTime":15000},"update-searchcomponent":{"class":"solr.SpellCheckComponent","name":"spellcheck"},"delete-requesthandler":"/old","set-property":{"query.filterCache.size":1024}}"#
+        );
+    }
+
+    #[test]
+    fn test_param_set_changes_body() {
+        let changes = SolrParamSetChanges::new()
+            .set("facets", json!({"facet": "true", "facet.field": "genre"}))
+            .update("paging", json!({"rows": "20"}))
+            .delete("old")
+            .delete("older");
+
+        assert_eq!(
+            changes.to_string(),
+            r#"{"set":{"facets":{"facet":"true","facet.field":"genre"}},"update":{"paging":{"rows":"20"}},"delete":["old","older"]}"#
+        );
+    }
+}
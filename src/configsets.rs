@@ -0,0 +1,159 @@
+//! Configset management, via the Configsets API (/admin/configs)
+//!
+//! Configsets are uploaded as zip files, which `zip_directory` builds in memory from a local
+//! configset directory (the one holding solrconfig.xml and managed-schema). The upload, list and
+//! delete requests themselves come from `SolrCloudMethods`.
+//!
+//! ```no_run
+//! # use tokio::runtime::Runtime;
+//! use stellr::configsets::{zip_directory, SolrConfigSetUpload};
+//! use stellr::prelude::*;
+//! use stellr::response_types::SolrCollectionsAdminType;
+//!
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! let solr_client = stellr::ZkSolrClient::new("localhost:9983", "/")?;
+//! let upload = SolrConfigSetUpload {
+//!     overwrite: Some(true),
+//!     cleanup: Some(true),
+//!     ..SolrConfigSetUpload::new("films")
+//! };
+//! let solr_request = solr_client
+//!     .upload_configset(&upload)?
+//!     .body(zip_directory("configsets/films/conf")?);
+//!
+//! # let mut rt = Runtime::new().unwrap();
+//! let result_struct =
+//! # rt.block_on(async {
+//!     solr_request
+//!         .call::<SolrCollectionsAdminType>()
+//!         .await
+//! # })
+//!     ?;
+//!
+//! assert!(result_struct.is_success());
+//! # Ok(()) }
+//! ```
+//!
+//! With a ZkSolrClient, individual configset files can also be read and written directly in
+//! zookeeper (eg. `ZkSolrClient::read_config_file`), without going through a solr node.
+
+use std::fs;
+use std::io::{self, Cursor, Write};
+use std::path::Path;
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::errors::SolrResult;
+
+/// Parameters for a configset upload (action=UPLOAD)
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SolrConfigSetUpload {
+    pub name: String,
+    /// Replace the files of an existing configset (overwrite)
+    pub overwrite: Option<bool>,
+    /// When overwriting, remove any existing files which are not in the upload (cleanup)
+    pub cleanup: Option<bool>,
+    /// Upload a single file to this path within the configset, instead of a zip (filePath)
+    pub file_path: Option<String>,
+}
+
+impl SolrConfigSetUpload {
+    pub fn new(name: &str) -> Self {
+        SolrConfigSetUpload {
+            name: String::from(name),
+            overwrite: None,
+            cleanup: None,
+            file_path: None,
+        }
+    }
+
+    /// Build the list of query parameters for this upload
+    pub(crate) fn query_params(&self) -> Vec<(String, String)> {
+        let mut params = vec![
+            (String::from("action"), String::from("UPLOAD")),
+            (String::from("name"), self.name.clone()),
+        ];
+        if let Some(overwrite) = self.overwrite {
+            params.push((String::from("overwrite"), overwrite.to_string()));
+        }
+        if let Some(cleanup) = self.cleanup {
+            params.push((String::from("cleanup"), cleanup.to_string()));
+        }
+        if let Some(file_path) = &self.file_path {
+            params.push((String::from("filePath"), file_path.clone()));
+        }
+        params
+    }
+}
+
+/// Zip up a local configset directory, in memory, ready for upload
+///
+/// Paths within the zip are relative to the directory, so it should be the directory holding
+/// solrconfig.xml (usually conf/) rather than its parent.
+pub fn zip_directory(directory: impl AsRef<Path>) -> SolrResult<Vec<u8>> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    add_directory(&mut zip, directory.as_ref(), "")?;
+    let cursor = zip.finish().map_err(io::Error::from)?;
+    Ok(cursor.into_inner())
+}
+
+fn add_directory(
+    zip: &mut ZipWriter<Cursor<Vec<u8>>>,
+    directory: &Path,
+    prefix: &str,
+) -> SolrResult<()> {
+    let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    let mut entries = fs::read_dir(directory)?.collect::<Result<Vec<_>, _>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        let name = format!("{}{}", prefix, entry.file_name().to_string_lossy());
+        if entry.file_type()?.is_dir() {
+            zip.add_directory(name.as_str(), options)
+                .map_err(io::Error::from)?;
+            add_directory(zip, &entry.path(), &format!("{}/", name))?;
+        } else {
+            zip.start_file(name.as_str(), options)
+                .map_err(io::Error::from)?;
+            zip.write_all(&fs::read(entry.path())?)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Read;
+    use zip::ZipArchive;
+
+    #[test]
+    fn test_zip_directory() {
+        let directory =
+            std::env::temp_dir().join(format!("stellr-configset-{}", std::process::id()));
+        fs::create_dir_all(directory.join("lang")).unwrap();
+        fs::write(directory.join("solrconfig.xml"), "<config/>").unwrap();
+        fs::write(directory.join("lang/stopwords_en.txt"), "a\nan\nthe\n").unwrap();
+
+        let zipped = zip_directory(&directory).unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+
+        let mut archive = ZipArchive::new(Cursor::new(zipped)).unwrap();
+        let mut names: Vec<String> = archive.file_names().map(String::from).collect();
+        names.sort();
+        assert_eq!(
+            names,
+            vec!["lang/", "lang/stopwords_en.txt", "solrconfig.xml"]
+        );
+
+        let mut stopwords = String::new();
+        archive
+            .by_name("lang/stopwords_en.txt")
+            .unwrap()
+            .read_to_string(&mut stopwords)
+            .unwrap();
+        assert_eq!(stopwords, "a\nan\nthe\n");
+    }
+}
@@ -0,0 +1,427 @@
+//! Typed requests for the Core Admin API (/admin/cores), for standalone (non-cloud) solr
+//!
+//! This follows the same shape as the `collections_admin` module: each action is a struct
+//! implementing `SolrCoreAdminAction`, and is either built with `SolrCoreMethods::core_admin`, or
+//! sent in one step with `run_core_admin_action`.
+//!
+//! ```no_run
+//! # use tokio::runtime::Runtime;
+//! use stellr::core_admin::{run_core_admin_action, SolrCoreStatus, SolrCreateCore};
+//! use stellr::DirectSolrClient;
+//!
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! let solr_client = DirectSolrClient::new("http://localhost:8983/solr")?;
+//! let create = SolrCreateCore {
+//!     config_set: Some(String::from("_default")),
+//!     ..SolrCreateCore::new("films")
+//! };
+//!
+//! # let mut rt = Runtime::new().unwrap();
+//! # rt.block_on(async {
+//! let response = run_core_admin_action(&solr_client, &create).await?;
+//! assert!(response.is_success());
+//!
+//! let status = run_core_admin_action(&solr_client, &SolrCoreStatus::core("films")).await?;
+//! let num_docs = status.core("films").and_then(|core| core.index.as_ref()?.numDocs);
+//! # Ok::<(), stellr::SolrError>(())
+//! # })?;
+//! # Ok(()) }
+//! ```
+//!
+//! Cores of a SolrCloud collection should be managed with the Collections API instead.
+
+use serde::de::DeserializeOwned;
+use std::collections::BTreeMap;
+
+use crate::clients::SolrCoreMethods;
+use crate::collections_admin::ParamList;
+use crate::errors::SolrResult;
+use crate::requests::SolrRequest;
+use crate::response_types::{SolrCoreAdminType, SolrCoreStatusType};
+
+/// A single Core Admin API action, along with its parameters
+pub trait SolrCoreAdminAction {
+    /// The struct solr's response is deserialised into
+    type Response: DeserializeOwned;
+
+    /// Value of the action parameter, eg. CREATE
+    fn action(&self) -> &'static str;
+
+    /// Parameters for this action, not including the action itself
+    fn query_params(&self) -> Vec<(String, String)>;
+}
+
+/// Send a Core Admin API action and deserialise the response into its typed response struct
+///
+/// As with the Collections API, failures are reported in the response body, so check the
+/// returned response as well.
+#[cfg(not(feature = "blocking"))]
+pub async fn run_core_admin_action<C, A>(client: &C, action: &A) -> SolrResult<A::Response>
+where
+    C: SolrCoreMethods,
+    A: SolrCoreAdminAction,
+{
+    client.core_admin(action)?.call::<A::Response>().await
+}
+
+/// Send a Core Admin API action and deserialise the response into its typed response struct
+///
+/// As with the Collections API, failures are reported in the response body, so check the
+/// returned response as well.
+#[cfg(feature = "blocking")]
+pub fn run_core_admin_action<C, A>(client: &C, action: &A) -> SolrResult<A::Response>
+where
+    C: SolrCoreMethods,
+    A: SolrCoreAdminAction,
+{
+    client.core_admin(action)?.call::<A::Response>()
+}
+
+/// Create a new core (action=CREATE)
+///
+/// The instance directory must either already hold a conf/ directory, or config_set must name a
+/// configset from solr's configset base directory.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SolrCreateCore {
+    pub name: String,
+    /// Directory for the core, relative to solr home (instanceDir, defaults to the name)
+    pub instance_dir: Option<String>,
+    /// Name of the solrconfig file within the conf directory (config)
+    pub config: Option<String>,
+    /// Name of the schema file within the conf directory (schema)
+    pub schema: Option<String>,
+    /// Index directory, relative to the instance directory (dataDir)
+    pub data_dir: Option<String>,
+    /// Shared configset to use, instead of the instance directory's conf (configSet)
+    pub config_set: Option<String>,
+    pub load_on_startup: Option<bool>,
+    pub transient: Option<bool>,
+    /// Core properties (property.<name>)
+    pub properties: BTreeMap<String, String>,
+}
+
+impl SolrCreateCore {
+    pub fn new(name: &str) -> Self {
+        SolrCreateCore {
+            name: String::from(name),
+            instance_dir: None,
+            config: None,
+            schema: None,
+            data_dir: None,
+            config_set: None,
+            load_on_startup: None,
+            transient: None,
+            properties: BTreeMap::new(),
+        }
+    }
+}
+
+impl SolrCoreAdminAction for SolrCreateCore {
+    type Response = SolrCoreAdminType;
+
+    fn action(&self) -> &'static str {
+        "CREATE"
+    }
+
+    fn query_params(&self) -> Vec<(String, String)> {
+        ParamList::default()
+            .push("name", &self.name)
+            .push_opt("instanceDir", &self.instance_dir)
+            .push_opt("config", &self.config)
+            .push_opt("schema", &self.schema)
+            .push_opt("dataDir", &self.data_dir)
+            .push_opt("configSet", &self.config_set)
+            .push_opt("loadOnStartup", &self.load_on_startup)
+            .push_opt("transient", &self.transient)
+            .push_properties(&self.properties)
+            .finish()
+    }
+}
+
+/// Remove a core from solr, optionally deleting its files (action=UNLOAD)
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SolrUnloadCore {
+    pub core: String,
+    /// Delete the index (deleteIndex)
+    pub delete_index: Option<bool>,
+    /// Delete the data directory, including the index and transaction log (deleteDataDir)
+    pub delete_data_dir: Option<bool>,
+    /// Delete the whole instance directory (deleteInstanceDir)
+    pub delete_instance_dir: Option<bool>,
+}
+
+impl SolrUnloadCore {
+    pub fn new(core: &str) -> Self {
+        SolrUnloadCore {
+            core: String::from(core),
+            delete_index: None,
+            delete_data_dir: None,
+            delete_instance_dir: None,
+        }
+    }
+}
+
+impl SolrCoreAdminAction for SolrUnloadCore {
+    type Response = SolrCoreAdminType;
+
+    fn action(&self) -> &'static str {
+        "UNLOAD"
+    }
+
+    fn query_params(&self) -> Vec<(String, String)> {
+        ParamList::default()
+            .push("core", &self.core)
+            .push_opt("deleteIndex", &self.delete_index)
+            .push_opt("deleteDataDir", &self.delete_data_dir)
+            .push_opt("deleteInstanceDir", &self.delete_instance_dir)
+            .finish()
+    }
+}
+
+/// Reload a core, eg. after a config or schema change (action=RELOAD)
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SolrReloadCore {
+    pub core: String,
+}
+
+impl SolrReloadCore {
+    pub fn new(core: &str) -> Self {
+        SolrReloadCore {
+            core: String::from(core),
+        }
+    }
+}
+
+impl SolrCoreAdminAction for SolrReloadCore {
+    type Response = SolrCoreAdminType;
+
+    fn action(&self) -> &'static str {
+        "RELOAD"
+    }
+
+    fn query_params(&self) -> Vec<(String, String)> {
+        vec![(String::from("core"), self.core.clone())]
+    }
+}
+
+/// Change the name of a core (action=RENAME)
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SolrRenameCore {
+    pub core: String,
+    pub other: String,
+}
+
+impl SolrRenameCore {
+    pub fn new(core: &str, other: &str) -> Self {
+        SolrRenameCore {
+            core: String::from(core),
+            other: String::from(other),
+        }
+    }
+}
+
+impl SolrCoreAdminAction for SolrRenameCore {
+    type Response = SolrCoreAdminType;
+
+    fn action(&self) -> &'static str {
+        "RENAME"
+    }
+
+    fn query_params(&self) -> Vec<(String, String)> {
+        ParamList::default()
+            .push("core", &self.core)
+            .push("other", &self.other)
+            .finish()
+    }
+}
+
+/// Atomically swap the names of two cores, eg. to switch to a rebuilt index (action=SWAP)
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SolrSwapCores {
+    pub core: String,
+    pub other: String,
+}
+
+impl SolrSwapCores {
+    pub fn new(core: &str, other: &str) -> Self {
+        SolrSwapCores {
+            core: String::from(core),
+            other: String::from(other),
+        }
+    }
+}
+
+impl SolrCoreAdminAction for SolrSwapCores {
+    type Response = SolrCoreAdminType;
+
+    fn action(&self) -> &'static str {
+        "SWAP"
+    }
+
+    fn query_params(&self) -> Vec<(String, String)> {
+        ParamList::default()
+            .push("core", &self.core)
+            .push("other", &self.other)
+            .finish()
+    }
+}
+
+/// Fetch the status of one or all cores (action=STATUS)
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct SolrCoreStatus {
+    /// Limit the status to a single core, rather than all cores
+    pub core: Option<String>,
+    /// Set to false to skip the (slower to gather) index statistics (indexInfo)
+    pub index_info: Option<bool>,
+}
+
+impl SolrCoreStatus {
+    /// Status of a single core
+    pub fn core(core: &str) -> Self {
+        SolrCoreStatus {
+            core: Some(String::from(core)),
+            index_info: None,
+        }
+    }
+}
+
+impl SolrCoreAdminAction for SolrCoreStatus {
+    type Response = SolrCoreStatusType;
+
+    fn action(&self) -> &'static str {
+        "STATUS"
+    }
+
+    fn query_params(&self) -> Vec<(String, String)> {
+        ParamList::default()
+            .push_opt("core", &self.core)
+            .push_opt("indexInfo", &self.index_info)
+            .finish()
+    }
+}
+
+/// Merge indexes into a core, from other cores or index directories (action=MERGEINDEXES)
+///
+/// The target core should be committed afterwards, and the source indexes should not be
+/// written to while the merge runs.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SolrMergeIndexes {
+    pub core: String,
+    /// Index directories to merge in (indexDir)
+    pub index_dirs: Vec<String>,
+    /// Cores to merge in (srcCore)
+    pub src_cores: Vec<String>,
+}
+
+impl SolrMergeIndexes {
+    /// Merge the indexes of other cores into a core
+    pub fn from_cores(core: &str, src_cores: &[&str]) -> Self {
+        SolrMergeIndexes {
+            core: String::from(core),
+            index_dirs: Vec::new(),
+            src_cores: src_cores.iter().map(|s| String::from(*s)).collect(),
+        }
+    }
+
+    /// Merge index directories into a core
+    pub fn from_dirs(core: &str, index_dirs: &[&str]) -> Self {
+        SolrMergeIndexes {
+            core: String::from(core),
+            index_dirs: index_dirs.iter().map(|s| String::from(*s)).collect(),
+            src_cores: Vec::new(),
+        }
+    }
+}
+
+impl SolrCoreAdminAction for SolrMergeIndexes {
+    type Response = SolrCoreAdminType;
+
+    fn action(&self) -> &'static str {
+        "MERGEINDEXES"
+    }
+
+    fn query_params(&self) -> Vec<(String, String)> {
+        // both parameters are repeated, rather than comma separated
+        let mut params = ParamList::default();
+        params.push("core", &self.core);
+        for index_dir in &self.index_dirs {
+            params.push("indexDir", index_dir);
+        }
+        for src_core in &self.src_cores {
+            params.push("srcCore", src_core);
+        }
+        params.finish()
+    }
+}
+
+/// Ask a core to recover, by replicating its index from its leader (action=REQUESTRECOVERY)
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SolrRequestRecovery {
+    pub core: String,
+}
+
+impl SolrRequestRecovery {
+    pub fn new(core: &str) -> Self {
+        SolrRequestRecovery {
+            core: String::from(core),
+        }
+    }
+}
+
+impl SolrCoreAdminAction for SolrRequestRecovery {
+    type Response = SolrCoreAdminType;
+
+    fn action(&self) -> &'static str {
+        "REQUESTRECOVERY"
+    }
+
+    fn query_params(&self) -> Vec<(String, String)> {
+        vec![(String::from("core"), self.core.clone())]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn pairs(params: &[(&str, &str)]) -> Vec<(String, String)> {
+        params
+            .iter()
+            .map(|(name, value)| (String::from(*name), String::from(*value)))
+            .collect()
+    }
+
+    #[test]
+    fn test_unload_params() {
+        let unload = SolrUnloadCore {
+            delete_index: Some(true),
+            delete_data_dir: Some(true),
+            ..SolrUnloadCore::new("films")
+        };
+
+        assert_eq!(unload.action(), "UNLOAD");
+        assert_eq!(
+            unload.query_params(),
+            pairs(&[
+                ("core", "films"),
+                ("deleteIndex", "true"),
+                ("deleteDataDir", "true")
+            ])
+        );
+    }
+
+    #[test]
+    fn test_merge_params() {
+        let merge = SolrMergeIndexes::from_cores("films", &["films_2019", "films_2020"]);
+
+        assert_eq!(
+            merge.query_params(),
+            pairs(&[
+                ("core", "films"),
+                ("srcCore", "films_2019"),
+                ("srcCore", "films_2020")
+            ])
+        );
+    }
+}
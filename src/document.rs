@@ -0,0 +1,150 @@
+/// Mapping between a user-defined struct and the solr fields it holds
+///
+/// This is normally implemented with `#[derive(SolrDocument)]` (using the `derive` feature),
+/// which also implements serde's Serialize and Deserialize traits using the same field mapping,
+/// so no serde renames are needed to match solr field names.
+///
+/// ```ignore
+/// use stellr::SolrDocument;
+/// use std::collections::HashMap;
+///
+/// #[derive(Debug, SolrDocument)]
+/// struct Product {
+///     #[solr(unique_key)]
+///     id: String,
+///     #[solr(rename = "inStock")]
+///     in_stock: bool,
+///     popularity: Option<u32>,
+///     features: Vec<String>,
+///     #[solr(dynamic = "attr_*")]
+///     attributes: HashMap<String, Vec<String>>,
+/// }
+///
+/// assert_eq!(Product::fl(), "id,inStock,popularity,features,attr_*");
+/// ```
+///
+/// The field list can then be used to fill in the fl parameter of a select request, with
+/// `SolrRequestBuilder::fl_for::<Product>()`.
+pub trait SolrDocument {
+    /// Names of the (non-dynamic) solr fields held by this type
+    fn solr_fields() -> &'static [&'static str];
+
+    /// Dynamic field patterns (eg. `attr_*`) held by this type
+    fn dynamic_field_patterns() -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Name of the field holding the uniqueKey, if it is part of this type
+    fn unique_key_field() -> Option<&'static str> {
+        None
+    }
+
+    /// The uniqueKey value for this document, if it is part of this type
+    fn unique_key(&self) -> Option<String> {
+        None
+    }
+
+    /// Comma separated list of fields for use as a field list (fl) parameter
+    fn fl() -> String {
+        Self::solr_fields()
+            .iter()
+            .chain(Self::dynamic_field_patterns().iter())
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+/// Support functions for code generated by `#[derive(SolrDocument)]`
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+pub mod derive_support {
+    pub use serde;
+
+    use serde::de::DeserializeOwned;
+    use serde_json::Value;
+    use std::collections::BTreeMap;
+    use std::iter::FromIterator;
+
+    /// All fields of a document, before they are assigned to struct fields
+    pub type SolrFields = BTreeMap<String, Value>;
+
+    /// Remove a field from the document, failing if it is missing and not an Option
+    pub fn take_field<T: DeserializeOwned>(
+        fields: &mut SolrFields,
+        name: &str,
+        multi_valued: bool,
+    ) -> Result<T, String> {
+        match fields.remove(name) {
+            Some(value) => parse_field(name, value, multi_valued),
+            None => {
+                serde_json::from_value(Value::Null).map_err(|_| format!("missing field `{}`", name))
+            }
+        }
+    }
+
+    /// Remove a field from the document, using the default value if it is missing
+    pub fn take_field_or_default<T: DeserializeOwned + Default>(
+        fields: &mut SolrFields,
+        name: &str,
+        multi_valued: bool,
+    ) -> Result<T, String> {
+        match fields.remove(name) {
+            Some(value) => parse_field(name, value, multi_valued),
+            None => Ok(T::default()),
+        }
+    }
+
+    /// Remove all fields matching a dynamic field pattern, keyed by the wildcard part of the name
+    pub fn take_dynamic_fields<C, V>(fields: &mut SolrFields, pattern: &str) -> Result<C, String>
+    where
+        C: FromIterator<(String, V)>,
+        V: DeserializeOwned,
+    {
+        let names: Vec<String> = fields
+            .keys()
+            .filter(|name| dynamic_field_key(pattern, name).is_some())
+            .cloned()
+            .collect();
+
+        names
+            .into_iter()
+            .map(|name| {
+                let value = fields.remove(&name).unwrap();
+                let key = dynamic_field_key(pattern, &name).unwrap().to_string();
+                serde_json::from_value(value)
+                    .map(|value| (key, value))
+                    .map_err(|e| format!("field `{}`: {}", name, e))
+            })
+            .collect()
+    }
+
+    /// Build a full field name from a dynamic field pattern and key
+    pub fn dynamic_field_name(pattern: &str, key: &str) -> String {
+        pattern.replacen('*', key, 1)
+    }
+
+    /// Extract the wildcard part of a field name, if it matches a dynamic field pattern
+    fn dynamic_field_key<'a>(pattern: &str, name: &'a str) -> Option<&'a str> {
+        if let Some(suffix) = pattern.strip_prefix('*') {
+            name.strip_suffix(suffix)
+        } else if let Some(prefix) = pattern.strip_suffix('*') {
+            name.strip_prefix(prefix)
+        } else {
+            None
+        }
+    }
+
+    fn parse_field<T: DeserializeOwned>(
+        name: &str,
+        value: Value,
+        multi_valued: bool,
+    ) -> Result<T, String> {
+        let value = match value {
+            Value::Array(_) | Value::Null => value,
+            single if multi_valued => Value::Array(vec![single]),
+            single => single,
+        };
+        serde_json::from_value(value).map_err(|e| format!("field `{}`: {}", name, e))
+    }
+}
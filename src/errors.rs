@@ -19,6 +19,14 @@ pub enum SolrError {
     ResponseParseError(serde_json::error::Error),
     /// Zookeeper access errors
     ZookeeperError(zookeeper::ZkError),
+    /// Documents or requests which cannot be matched to a shard (or shard leader)
+    RoutingError(String),
+    /// Async admin requests which could not be submitted, were lost, or did not finish in time
+    AsyncRequestError(String),
+    /// Local file access errors, eg. when zipping or downloading configsets
+    IOError(std::io::Error),
+    /// Invalid schema definitions, or schema changes which solr rejected or would need a reindex
+    SchemaError(String),
 }
 
 impl std::error::Error for SolrError {
@@ -32,6 +40,10 @@ impl std::error::Error for SolrError {
             SolrError::PayloadNotAJsonArrayError => None,
             SolrError::ResponseParseError(_) => None,
             SolrError::ZookeeperError(_) => None,
+            SolrError::RoutingError(_) => None,
+            SolrError::AsyncRequestError(_) => None,
+            SolrError::IOError(_) => None,
+            SolrError::SchemaError(_) => None,
         }
     }
 }
@@ -49,6 +61,12 @@ impl std::fmt::Display for SolrError {
             SolrError::HostParseError(ref error) => error.fmt(f),
             SolrError::ResponseParseError(ref error) => error.fmt(f),
             SolrError::ZookeeperError(ref error) => error.fmt(f),
+            SolrError::RoutingError(ref message) => write!(f, "Routing failed: {}", message),
+            SolrError::AsyncRequestError(ref message) => {
+                write!(f, "Async request failed: {}", message)
+            }
+            SolrError::IOError(ref error) => error.fmt(f),
+            SolrError::SchemaError(ref message) => write!(f, "Schema update failed: {}", message),
         }
     }
 }
@@ -76,3 +94,9 @@ impl From<zookeeper::ZkError> for SolrError {
         SolrError::ZookeeperError(err)
     }
 }
+
+impl From<std::io::Error> for SolrError {
+    fn from(err: std::io::Error) -> SolrError {
+        SolrError::IOError(err)
+    }
+}
@@ -0,0 +1,85 @@
+//! Health checks across every live node of a SolrCloud cluster
+//!
+//! `SolrCoreMethods::health` checks whichever node a client picks, so is enough for a single
+//! node's readiness probe. For a cluster wide check, `probe_live_nodes` sends the same request to
+//! each node listed under live_nodes in zookeeper, and reports each node's result separately.
+//!
+//! ```no_run
+//! # use tokio::runtime::Runtime;
+//! use stellr::health::probe_live_nodes;
+//! use stellr::ZkSolrClient;
+//!
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! let solr_client = ZkSolrClient::new("localhost:9983", "/")?;
+//!
+//! # let mut rt = Runtime::new().unwrap();
+//! let probes =
+//! # rt.block_on(async {
+//!     probe_live_nodes(&solr_client, true)
+//!     .await
+//! # })
+//!     ?;
+//!
+//! for (node_name, health) in &probes {
+//!     match health {
+//!         Ok(health) if health.is_healthy() => println!("{}: ok", node_name),
+//!         Ok(health) => println!("{}: {:?}", node_name, health.message),
+//!         Err(e) => println!("{}: unreachable ({})", node_name, e),
+//!     }
+//! }
+//! # Ok(()) }
+//! ```
+
+use crate::clients::{SolrCoreMethods, ZkSolrClient};
+use crate::errors::SolrResult;
+use crate::requests::SolrRequest;
+use crate::response_types::SolrHealthType;
+
+#[cfg(not(feature = "blocking"))]
+use futures::future::join_all;
+
+/// Run the health check against every live node, concurrently
+///
+/// Only failing to read live_nodes from zookeeper is an error here: requests to individual nodes
+/// which fail are reported as that node's result.
+#[cfg(not(feature = "blocking"))]
+pub async fn probe_live_nodes(
+    client: &ZkSolrClient,
+    require_healthy_cores: bool,
+) -> SolrResult<Vec<(String, SolrResult<SolrHealthType>)>> {
+    let probes =
+        client
+            .live_node_clients()?
+            .into_iter()
+            .map(|(node_name, node_client)| async move {
+                let health = match node_client.health(require_healthy_cores) {
+                    Ok(request) => request.call::<SolrHealthType>().await,
+                    Err(e) => Err(e),
+                };
+                (node_name, health)
+            });
+
+    Ok(join_all(probes).await)
+}
+
+/// Run the health check against every live node, in turn
+///
+/// Only failing to read live_nodes from zookeeper is an error here: requests to individual nodes
+/// which fail are reported as that node's result.
+#[cfg(feature = "blocking")]
+pub fn probe_live_nodes(
+    client: &ZkSolrClient,
+    require_healthy_cores: bool,
+) -> SolrResult<Vec<(String, SolrResult<SolrHealthType>)>> {
+    Ok(client
+        .live_node_clients()?
+        .into_iter()
+        .map(|(node_name, node_client)| {
+            let health = node_client
+                .health(require_healthy_cores)
+                .and_then(|request| request.call::<SolrHealthType>());
+            (node_name, health)
+        })
+        .collect())
+}
@@ -130,9 +130,13 @@
 //!
 //! ## Package Features
 //!
-//! The crate only has one optional feature:
+//! The crate has the following optional features:
 //!
 //! * `blocking` - use the blocking feature of the reqwest library, rather than the async version
+//! * `derive` - provide `#[derive(SolrDocument)]`, to map user-defined structs onto solr fields
+//! * `chrono` - convert solr dates to chrono types, with `serde_helpers::chrono_date`
+//! * `time` - convert solr dates to time types, with `serde_helpers::time_date`
+//! * `toml` - read declarative schema migrations from TOML, with `schema_migration::SolrSchemaMigration::from_toml`
 //!
 //! ## Errors
 //!
@@ -150,13 +154,37 @@ pub use clients::{
     DirectSolrClient, SolrClientBuilder, SolrCloudMethods, SolrCoreMethods, ZkSolrClient,
 };
 pub use config::SolrClientConfig;
+#[cfg(feature = "derive")]
+pub use document::derive_support;
+pub use document::SolrDocument;
 pub use errors::{SolrError, SolrResult};
 #[doc(inline)]
-pub use requests::{SolrRequest, SolrRequestBuilder};
+pub use requests::{
+    bisect_update, PayloadFormat, SolrAddOptions, SolrChildTransformer, SolrCsvOptions,
+    SolrExtractFormat, SolrExtractOptions, SolrLabelledDocument, SolrLukeOptions, SolrLukeShow,
+    SolrNestedDocument, SolrRequest, SolrRequestBuilder, SolrUpdateCommands,
+};
+
+#[cfg(feature = "derive")]
+#[doc(inline)]
+pub use stellr_derive::SolrDocument;
 
 mod clients;
+pub mod collections_admin;
 mod config;
+pub mod config_api;
+pub mod configsets;
+pub mod core_admin;
+mod document;
 mod errors;
+pub mod health;
+pub mod managed_resources;
+pub mod metrics;
 pub mod prelude;
+pub mod replication;
 mod requests;
 pub mod response_types;
+pub mod schema;
+pub mod schema_migration;
+pub mod security;
+pub mod serde_helpers;
@@ -0,0 +1,442 @@
+//! Managed synonyms and stopwords, via solr's managed resources REST API
+//!
+//! Fields analysed with ManagedSynonymGraphFilterFactory or ManagedStopFilterFactory read their
+//! synonyms or stopwords from a named managed resource (eg. /schema/analysis/synonyms/english),
+//! which is edited with the `SolrCoreMethods` helpers (eg. `add_synonyms` or `delete_stopword`).
+//! Edits are stored straight away, but only change analysis once the collection is reloaded.
+//!
+//! To keep a resource in line with a list maintained elsewhere, `replace_synonyms` and
+//! `replace_stopwords` work out the fewest changes from the current contents, apply them, and
+//! reload the collection if anything changed.
+//!
+//! ```no_run
+//! # use tokio::runtime::Runtime;
+//! use stellr::managed_resources::{replace_synonyms, SolrSynonymMappings};
+//!
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! let synonyms = SolrSynonymMappings::new()
+//!     .one_way("gb", &["gib", "gigabyte"])
+//!     .equivalent(&["tv", "television", "telly"]);
+//!
+//! let solr_client = stellr::ZkSolrClient::new("localhost:9983", "/")?;
+//!
+//! # let mut rt = Runtime::new().unwrap();
+//! let changes =
+//! # rt.block_on(async {
+//!     replace_synonyms(&solr_client, "products", "english", &synonyms)
+//!     .await
+//! # })
+//!     ?;
+//!
+//! println!("removed {:?}, added {:?}", changes.delete, changes.add);
+//! # Ok(()) }
+//! ```
+
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::clients::SolrCloudMethods;
+use crate::collections_admin::{run_collections_action, SolrReloadCollection};
+use crate::errors::{SolrError, SolrResult};
+use crate::requests::SolrRequest;
+use crate::response_types::{
+    SolrManagedStopwords, SolrManagedStopwordsType, SolrManagedSynonyms, SolrManagedSynonymsType,
+    SolrUpdateType,
+};
+
+/// Builder for a set of synonym mappings, from each term to the terms it expands to
+///
+/// This serializes to the map accepted by `SolrCoreMethods::add_synonyms`, with equivalent
+/// synonyms expanded into one mapping per term (the same way solr stores them).
+#[derive(Serialize, Clone, Debug, Default, Eq, PartialEq)]
+#[serde(transparent)]
+pub struct SolrSynonymMappings {
+    mappings: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl SolrSynonymMappings {
+    pub fn new() -> SolrSynonymMappings {
+        SolrSynonymMappings::default()
+    }
+
+    /// Map a term to its synonyms, without mapping them back to the term (eg. mad => angry)
+    pub fn one_way(mut self, term: &str, synonyms: &[&str]) -> SolrSynonymMappings {
+        self.mappings
+            .entry(String::from(term))
+            .or_default()
+            .extend(synonyms.iter().map(|synonym| String::from(*synonym)));
+        self
+    }
+
+    /// Treat all the terms as equivalent, mapping each one to the whole set
+    pub fn equivalent(mut self, terms: &[&str]) -> SolrSynonymMappings {
+        for term in terms {
+            self = self.one_way(term, terms);
+        }
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.mappings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mappings.is_empty()
+    }
+
+    /// Synonyms of a single term
+    pub fn get(&self, term: &str) -> Option<&BTreeSet<String>> {
+        self.mappings.get(term)
+    }
+
+    fn lowercase(&self) -> SolrSynonymMappings {
+        let mut lowercased = SolrSynonymMappings::new();
+        for (term, synonyms) in &self.mappings {
+            lowercased
+                .mappings
+                .entry(term.to_lowercase())
+                .or_default()
+                .extend(synonyms.iter().map(|synonym| synonym.to_lowercase()));
+        }
+        lowercased
+    }
+}
+
+impl From<&SolrManagedSynonyms> for SolrSynonymMappings {
+    fn from(managed: &SolrManagedSynonyms) -> Self {
+        SolrSynonymMappings {
+            mappings: managed
+                .managedMap
+                .iter()
+                .map(|(term, synonyms)| (term.clone(), synonyms.iter().cloned().collect()))
+                .collect(),
+        }
+    }
+}
+
+/// The changes needed to turn a managed synonym map into the desired one
+///
+/// Deletes are applied first, as adding to an existing mapping can only extend it: mappings
+/// which lose synonyms are deleted, then added back in full.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SolrSynonymsChanges {
+    pub delete: Vec<String>,
+    pub add: SolrSynonymMappings,
+}
+
+impl SolrSynonymsChanges {
+    pub fn is_empty(&self) -> bool {
+        self.delete.is_empty() && self.add.is_empty()
+    }
+}
+
+/// The changes needed to turn a managed stopword list into the desired one
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SolrStopwordsChanges {
+    pub delete: Vec<String>,
+    pub add: Vec<String>,
+}
+
+impl SolrStopwordsChanges {
+    pub fn is_empty(&self) -> bool {
+        self.delete.is_empty() && self.add.is_empty()
+    }
+}
+
+/// Work out the fewest changes to turn the current synonyms into the desired ones
+///
+/// If the current map ignores case, the desired terms are compared (and added) in lower case,
+/// as solr would store them.
+pub fn plan_synonyms_replacement(
+    current: &SolrManagedSynonyms,
+    desired: &SolrSynonymMappings,
+) -> SolrSynonymsChanges {
+    let desired = if current.initArgs.ignoreCase == Some(true) {
+        desired.lowercase()
+    } else {
+        desired.clone()
+    };
+    let current = SolrSynonymMappings::from(current);
+
+    let mut changes = SolrSynonymsChanges::default();
+    for (term, synonyms) in &current.mappings {
+        match desired.get(term) {
+            None => changes.delete.push(term.clone()),
+            Some(wanted) if !synonyms.is_subset(wanted) => changes.delete.push(term.clone()),
+            Some(_) => {}
+        }
+    }
+    for (term, wanted) in &desired.mappings {
+        let missing: BTreeSet<String> = match current.get(term) {
+            Some(synonyms) if synonyms.is_subset(wanted) => {
+                wanted.difference(synonyms).cloned().collect()
+            }
+            _ => wanted.clone(),
+        };
+        if !missing.is_empty() {
+            changes.add.mappings.insert(term.clone(), missing);
+        }
+    }
+    changes
+}
+
+/// Work out the fewest changes to turn the current stopwords into the desired ones
+///
+/// If the current list ignores case, the desired words are compared (and added) in lower case.
+pub fn plan_stopwords_replacement(
+    current: &SolrManagedStopwords,
+    desired: &[&str],
+) -> SolrStopwordsChanges {
+    let desired: BTreeSet<String> = desired
+        .iter()
+        .map(|word| {
+            if current.initArgs.ignoreCase == Some(true) {
+                word.to_lowercase()
+            } else {
+                String::from(*word)
+            }
+        })
+        .collect();
+    let current: BTreeSet<String> = current.managedList.iter().cloned().collect();
+
+    SolrStopwordsChanges {
+        delete: current.difference(&desired).cloned().collect(),
+        add: desired.difference(&current).cloned().collect(),
+    }
+}
+
+/// Replace the whole of a managed synonym map, then reload the collection if anything changed
+///
+/// Only the differences are sent: one delete per removed (or reduced) mapping, and a single
+/// request adding everything else. The applied changes are returned.
+#[cfg(not(feature = "blocking"))]
+pub async fn replace_synonyms<C: SolrCloudMethods>(
+    client: &C,
+    collection: &str,
+    name: &str,
+    desired: &SolrSynonymMappings,
+) -> SolrResult<SolrSynonymsChanges> {
+    let current = client
+        .managed_synonyms(collection, name)?
+        .call::<SolrManagedSynonymsType>()
+        .await?;
+    let changes = plan_synonyms_replacement(&current.synonymMappings, desired);
+    if changes.is_empty() {
+        return Ok(changes);
+    }
+
+    for term in &changes.delete {
+        let response = client
+            .delete_synonym(collection, name, term)?
+            .call::<SolrUpdateType>()
+            .await?;
+        check_response(&response)?;
+    }
+    if !changes.add.is_empty() {
+        let response = client
+            .add_synonyms(collection, name, &changes.add)?
+            .call::<SolrUpdateType>()
+            .await?;
+        check_response(&response)?;
+    }
+    reload_collection(client, collection).await?;
+    Ok(changes)
+}
+
+/// Replace the whole of a managed synonym map, then reload the collection if anything changed
+///
+/// Only the differences are sent: one delete per removed (or reduced) mapping, and a single
+/// request adding everything else. The applied changes are returned.
+#[cfg(feature = "blocking")]
+pub fn replace_synonyms<C: SolrCloudMethods>(
+    client: &C,
+    collection: &str,
+    name: &str,
+    desired: &SolrSynonymMappings,
+) -> SolrResult<SolrSynonymsChanges> {
+    let current = client
+        .managed_synonyms(collection, name)?
+        .call::<SolrManagedSynonymsType>()?;
+    let changes = plan_synonyms_replacement(&current.synonymMappings, desired);
+    if changes.is_empty() {
+        return Ok(changes);
+    }
+
+    for term in &changes.delete {
+        let response = client
+            .delete_synonym(collection, name, term)?
+            .call::<SolrUpdateType>()?;
+        check_response(&response)?;
+    }
+    if !changes.add.is_empty() {
+        let response = client
+            .add_synonyms(collection, name, &changes.add)?
+            .call::<SolrUpdateType>()?;
+        check_response(&response)?;
+    }
+    reload_collection(client, collection)?;
+    Ok(changes)
+}
+
+/// Replace the whole of a managed stopword list, then reload the collection if anything changed
+///
+/// Only the differences are sent: one delete per removed word, and a single request adding the
+/// new words. The applied changes are returned.
+#[cfg(not(feature = "blocking"))]
+pub async fn replace_stopwords<C: SolrCloudMethods>(
+    client: &C,
+    collection: &str,
+    name: &str,
+    desired: &[&str],
+) -> SolrResult<SolrStopwordsChanges> {
+    let current = client
+        .managed_stopwords(collection, name)?
+        .call::<SolrManagedStopwordsType>()
+        .await?;
+    let changes = plan_stopwords_replacement(&current.wordSet, desired);
+    if changes.is_empty() {
+        return Ok(changes);
+    }
+
+    for word in &changes.delete {
+        let response = client
+            .delete_stopword(collection, name, word)?
+            .call::<SolrUpdateType>()
+            .await?;
+        check_response(&response)?;
+    }
+    if !changes.add.is_empty() {
+        let words: Vec<&str> = changes.add.iter().map(String::as_str).collect();
+        let response = client
+            .add_stopwords(collection, name, &words)?
+            .call::<SolrUpdateType>()
+            .await?;
+        check_response(&response)?;
+    }
+    reload_collection(client, collection).await?;
+    Ok(changes)
+}
+
+/// Replace the whole of a managed stopword list, then reload the collection if anything changed
+///
+/// Only the differences are sent: one delete per removed word, and a single request adding the
+/// new words. The applied changes are returned.
+#[cfg(feature = "blocking")]
+pub fn replace_stopwords<C: SolrCloudMethods>(
+    client: &C,
+    collection: &str,
+    name: &str,
+    desired: &[&str],
+) -> SolrResult<SolrStopwordsChanges> {
+    let current = client
+        .managed_stopwords(collection, name)?
+        .call::<SolrManagedStopwordsType>()?;
+    let changes = plan_stopwords_replacement(&current.wordSet, desired);
+    if changes.is_empty() {
+        return Ok(changes);
+    }
+
+    for word in &changes.delete {
+        let response = client
+            .delete_stopword(collection, name, word)?
+            .call::<SolrUpdateType>()?;
+        check_response(&response)?;
+    }
+    if !changes.add.is_empty() {
+        let words: Vec<&str> = changes.add.iter().map(String::as_str).collect();
+        let response = client
+            .add_stopwords(collection, name, &words)?
+            .call::<SolrUpdateType>()?;
+        check_response(&response)?;
+    }
+    reload_collection(client, collection)?;
+    Ok(changes)
+}
+
+/// Reload a collection, so analysis picks up changes to its managed resources
+#[cfg(not(feature = "blocking"))]
+pub async fn reload_collection<C: SolrCloudMethods>(
+    client: &C,
+    collection: &str,
+) -> SolrResult<()> {
+    let response = run_collections_action(client, &SolrReloadCollection::new(collection)).await?;
+    check_reload(collection, response.is_success())
+}
+
+/// Reload a collection, so analysis picks up changes to its managed resources
+#[cfg(feature = "blocking")]
+pub fn reload_collection<C: SolrCloudMethods>(client: &C, collection: &str) -> SolrResult<()> {
+    let response = run_collections_action(client, &SolrReloadCollection::new(collection))?;
+    check_reload(collection, response.is_success())
+}
+
+fn check_reload(collection: &str, success: bool) -> SolrResult<()> {
+    if success {
+        Ok(())
+    } else {
+        Err(SolrError::SchemaError(format!(
+            "reload of {} failed after changing managed resources",
+            collection
+        )))
+    }
+}
+
+fn check_response(response: &SolrUpdateType) -> SolrResult<()> {
+    match &response.error {
+        None => Ok(()),
+        Some(error) => Err(SolrError::SchemaError(
+            error.msg.clone().unwrap_or_default(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_plan_synonyms_replacement() {
+        let current: SolrManagedSynonyms = serde_json::from_value(json!({
+            "initArgs": {"ignoreCase": true},
+            "initializedOn": "2020-10-01T09:12:30.123Z",
+            "managedMap": {
+                "gb": ["gib", "gigabyte"],
+                "mad": ["angry", "upset"],
+                "tv": ["television", "tv"],
+                "television": ["television", "tv"],
+                "old": ["stale"]
+            }
+        }))
+        .unwrap();
+        let desired = SolrSynonymMappings::new()
+            .one_way("GB", &["GiB", "gigabyte", "gigabytes"])
+            .one_way("mad", &["angry"])
+            .equivalent(&["tv", "television"])
+            .one_way("happy", &["glad"]);
+
+        let changes = plan_synonyms_replacement(&current, &desired);
+        assert_eq!(changes.delete, vec!["mad", "old"]);
+        assert_eq!(
+            serde_json::to_value(&changes.add).unwrap(),
+            json!({"gb": ["gigabytes"], "happy": ["glad"], "mad": ["angry"]})
+        );
+
+        let unchanged = SolrSynonymMappings::from(&current);
+        assert!(plan_synonyms_replacement(&current, &unchanged).is_empty());
+    }
+
+    #[test]
+    fn test_plan_stopwords_replacement() {
+        let current: SolrManagedStopwords = serde_json::from_value(json!({
+            "initArgs": {"ignoreCase": false},
+            "managedList": ["a", "an", "the"]
+        }))
+        .unwrap();
+
+        let changes = plan_stopwords_replacement(&current, &["a", "The", "of"]);
+        assert_eq!(changes.delete, vec!["an", "the"]);
+        assert_eq!(changes.add, vec!["The", "of"]);
+    }
+}
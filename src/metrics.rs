@@ -0,0 +1,357 @@
+//! Metrics API requests (/admin/metrics), and Prometheus text export
+//!
+//! Metrics are requested with `SolrCoreMethods::metrics`, filtered by a `SolrMetricsOptions`, and
+//! deserialised into `response_types::SolrMetricsType`. `prometheus_text` then renders them in
+//! the Prometheus text exposition format, eg. for a scrape endpoint.
+//!
+//! ```no_run
+//! # use tokio::runtime::Runtime;
+//! use stellr::metrics::{prometheus_text, SolrMetricKind, SolrMetricsGroup, SolrMetricsOptions};
+//! use stellr::prelude::*;
+//! use stellr::response_types::SolrMetricsType;
+//!
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! let options = SolrMetricsOptions {
+//!     groups: vec![SolrMetricsGroup::Core],
+//!     kinds: vec![SolrMetricKind::Timer],
+//!     prefixes: vec![String::from("QUERY./select")],
+//!     ..Default::default()
+//! };
+//!
+//! let solr_client = stellr::DirectSolrClient::new("http://localhost:8983/solr")?;
+//! let solr_request = solr_client.metrics(&options)?;
+//!
+//! # let mut rt = Runtime::new().unwrap();
+//! let result_struct =
+//! # rt.block_on(async {
+//!     solr_request
+//!         .call::<SolrMetricsType>()
+//!         .await
+//! # })
+//!     ?;
+//!
+//! print!("{}", prometheus_text(&result_struct));
+//! # Ok(()) }
+//! ```
+
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::collections_admin::ParamList;
+use crate::response_types::{SolrMetric, SolrMetricsType};
+
+/// Groups of metric registries (group)
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum SolrMetricsGroup {
+    All,
+    Jvm,
+    Jetty,
+    Node,
+    Core,
+}
+
+impl SolrMetricsGroup {
+    fn as_str(&self) -> &'static str {
+        match self {
+            SolrMetricsGroup::All => "all",
+            SolrMetricsGroup::Jvm => "jvm",
+            SolrMetricsGroup::Jetty => "jetty",
+            SolrMetricsGroup::Node => "node",
+            SolrMetricsGroup::Core => "core",
+        }
+    }
+}
+
+/// Kinds of metric (type)
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum SolrMetricKind {
+    All,
+    Counter,
+    Gauge,
+    Histogram,
+    Meter,
+    Timer,
+}
+
+impl SolrMetricKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            SolrMetricKind::All => "all",
+            SolrMetricKind::Counter => "counter",
+            SolrMetricKind::Gauge => "gauge",
+            SolrMetricKind::Histogram => "histogram",
+            SolrMetricKind::Meter => "meter",
+            SolrMetricKind::Timer => "timer",
+        }
+    }
+}
+
+/// Filters for a Metrics API request
+///
+/// Each list is sent as a comma separated parameter, and left out when empty. Metrics must match
+/// every filter given.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct SolrMetricsOptions {
+    /// Registry groups to report (group)
+    pub groups: Vec<SolrMetricsGroup>,
+    /// Metric kinds to report (type)
+    pub kinds: Vec<SolrMetricKind>,
+    /// Metric name prefixes, eg. QUERY./select (prefix)
+    pub prefixes: Vec<String>,
+    /// Regular expressions to match metric names against (regex)
+    pub regexes: Vec<String>,
+    /// Properties to return from compound metrics, eg. p99_ms (property)
+    pub properties: Vec<String>,
+    /// Exact metrics to return, as registry:metric (key), which overrides the other filters
+    pub keys: Vec<String>,
+    /// Set to false to return counters and gauges as objects, so they can be told apart (compact)
+    pub compact: Option<bool>,
+}
+
+impl SolrMetricsOptions {
+    /// Build the list of query parameters for these options
+    pub(crate) fn query_params(&self) -> Vec<(String, String)> {
+        let groups: Vec<String> = self.groups.iter().map(|g| g.as_str().into()).collect();
+        let kinds: Vec<String> = self.kinds.iter().map(|k| k.as_str().into()).collect();
+        let mut params = ParamList::default();
+        params
+            .push_list("group", &groups)
+            .push_list("type", &kinds)
+            .push_list("prefix", &self.prefixes)
+            .push_list("property", &self.properties)
+            .push_opt("compact", &self.compact);
+        // regexes and keys may contain commas, so are repeated instead
+        for regex in &self.regexes {
+            params.push("regex", regex);
+        }
+        for key in &self.keys {
+            params.push("key", key);
+        }
+        params.finish()
+    }
+}
+
+/// Render metrics in the Prometheus text exposition format
+///
+/// Metric names are prefixed with solr_ and reduced to the characters Prometheus allows, with the
+/// registry as a label, so the same metric from different cores shares a family. Timers and
+/// histograms become summaries (timers in seconds), meters and non-compact counters become
+/// counters, and numeric values become gauges. Non-numeric values are left out.
+pub fn prometheus_text(metrics: &SolrMetricsType) -> String {
+    let mut families: BTreeMap<String, (&'static str, Vec<String>)> = BTreeMap::new();
+    let mut sample = |name: String, kind: &'static str, labels: &str, value: f64| {
+        families
+            .entry(family_name(&name, kind))
+            .or_insert_with(|| (kind, Vec::new()))
+            .1
+            .push(format!("{}{{{}}} {}", name, labels, value));
+    };
+
+    for (registry, registry_metrics) in &metrics.metrics {
+        let registry_label = format!("registry=\"{}\"", escape_label(registry));
+        for (metric_name, metric) in registry_metrics {
+            let name = format!("solr_{}", sanitise(metric_name));
+            match metric {
+                SolrMetric::Timer(timer) => {
+                    let name = format!("{}_seconds", name);
+                    for (quantile, value) in &[
+                        ("0.5", timer.median_ms),
+                        ("0.75", timer.p75_ms),
+                        ("0.95", timer.p95_ms),
+                        ("0.99", timer.p99_ms),
+                        ("0.999", timer.p999_ms),
+                    ] {
+                        let labels = format!("{},quantile=\"{}\"", registry_label, quantile);
+                        sample(name.clone(), "summary", &labels, value / 1000.0);
+                    }
+                    if let Some(sum_ms) = timer.sum_ms {
+                        sample(
+                            format!("{}_sum", name),
+                            "summary",
+                            &registry_label,
+                            sum_ms / 1000.0,
+                        );
+                    }
+                    sample(
+                        format!("{}_count", name),
+                        "summary",
+                        &registry_label,
+                        timer.count as f64,
+                    );
+                }
+                SolrMetric::Histogram(histogram) => {
+                    for (quantile, value) in &[
+                        ("0.5", histogram.median),
+                        ("0.75", histogram.p75),
+                        ("0.95", histogram.p95),
+                        ("0.99", histogram.p99),
+                        ("0.999", histogram.p999),
+                    ] {
+                        let labels = format!("{},quantile=\"{}\"", registry_label, quantile);
+                        sample(name.clone(), "summary", &labels, *value);
+                    }
+                    sample(
+                        format!("{}_count", name),
+                        "summary",
+                        &registry_label,
+                        histogram.count as f64,
+                    );
+                }
+                SolrMetric::Meter(meter) => {
+                    sample(
+                        format!("{}_total", name),
+                        "counter",
+                        &registry_label,
+                        meter.count as f64,
+                    );
+                }
+                SolrMetric::Counter { count } => {
+                    sample(
+                        format!("{}_total", name),
+                        "counter",
+                        &registry_label,
+                        *count as f64,
+                    );
+                }
+                SolrMetric::Gauge {
+                    value: Value::Object(values),
+                }
+                | SolrMetric::Value(Value::Object(values)) => {
+                    // compound gauges, eg. cache statistics, become one gauge per numeric entry
+                    for (key, value) in values {
+                        if let Some(value) = SolrMetric::Value(value.clone()).as_f64() {
+                            let name = format!("{}_{}", name, sanitise(key));
+                            sample(name, "gauge", &registry_label, value);
+                        }
+                    }
+                }
+                SolrMetric::Gauge { .. } | SolrMetric::Value(_) => {
+                    if let Some(value) = metric.as_f64() {
+                        sample(name, "gauge", &registry_label, value);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut text = String::new();
+    for (family, (kind, samples)) in families {
+        let _ = writeln!(text, "# TYPE {} {}", family, kind);
+        for sample in samples {
+            let _ = writeln!(text, "{}", sample);
+        }
+    }
+    text
+}
+
+/// Summaries are declared under their base name, without _sum or _count, while counters keep
+/// their _total suffix
+fn family_name(name: &str, kind: &str) -> String {
+    if kind != "summary" {
+        return name.to_string();
+    }
+    ["_sum", "_count"]
+        .iter()
+        .find_map(|suffix| name.strip_suffix(suffix))
+        .unwrap_or(name)
+        .to_string()
+}
+
+/// Replace anything Prometheus does not allow in metric names with underscores
+fn sanitise(name: &str) -> String {
+    let mut sanitised = String::with_capacity(name.len());
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            sanitised.push(c);
+        } else if !sanitised.ends_with('_') {
+            sanitised.push('_');
+        }
+    }
+    sanitised.trim_matches('_').to_string()
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_metrics_params() {
+        let options = SolrMetricsOptions {
+            groups: vec![SolrMetricsGroup::Jvm, SolrMetricsGroup::Core],
+            prefixes: vec![String::from("memory.heap"), String::from("QUERY./select")],
+            keys: vec![String::from("solr.jvm:os.processCpuLoad")],
+            compact: Some(false),
+            ..Default::default()
+        };
+
+        let expected: Vec<(String, String)> = vec![
+            ("group", "jvm,core"),
+            ("prefix", "memory.heap,QUERY./select"),
+            ("compact", "false"),
+            ("key", "solr.jvm:os.processCpuLoad"),
+        ]
+        .into_iter()
+        .map(|(name, value)| (String::from(name), String::from(value)))
+        .collect();
+        assert_eq!(options.query_params(), expected);
+    }
+
+    #[test]
+    fn test_prometheus_text() {
+        let metrics: SolrMetricsType = serde_json::from_value(serde_json::json!({
+            "responseHeader": {"status": 0, "QTime": 3},
+            "metrics": {
+                "solr.core.films": {
+                    "QUERY./select.requests": {"count": 42},
+                    "QUERY./select.requestTimes": {
+                        "count": 42, "meanRate": 0.5, "1minRate": 0.4, "5minRate": 0.3,
+                        "15minRate": 0.2, "min_ms": 1.0, "max_ms": 90.0, "mean_ms": 8.0,
+                        "median_ms": 5.0, "stddev_ms": 3.0, "p75_ms": 10.0, "p95_ms": 20.0,
+                        "p99_ms": 50.0, "p999_ms": 90.0
+                    },
+                    "CACHE.searcher.filterCache": {"lookups": 10, "hitratio": 0.5, "warmupTime": 0},
+                    "CORE.coreName": "films"
+                },
+                "solr.jvm:memory.heap.used": 1048576,
+                "solr.jvm:threads.count": 48
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            metrics.metric("solr.jvm", "memory.heap.used"),
+            Some(&SolrMetric::Value(serde_json::json!(1048576)))
+        );
+        assert_eq!(
+            prometheus_text(&metrics),
+            "# TYPE solr_CACHE_searcher_filterCache_hitratio gauge\n\
+             solr_CACHE_searcher_filterCache_hitratio{registry=\"solr.core.films\"} 0.5\n\
+             # TYPE solr_CACHE_searcher_filterCache_lookups gauge\n\
+             solr_CACHE_searcher_filterCache_lookups{registry=\"solr.core.films\"} 10\n\
+             # TYPE solr_CACHE_searcher_filterCache_warmupTime gauge\n\
+             solr_CACHE_searcher_filterCache_warmupTime{registry=\"solr.core.films\"} 0\n\
+             # TYPE solr_QUERY_select_requestTimes_seconds summary\n\
+             solr_QUERY_select_requestTimes_seconds{registry=\"solr.core.films\",quantile=\"0.5\"} 0.005\n\
+             solr_QUERY_select_requestTimes_seconds{registry=\"solr.core.films\",quantile=\"0.75\"} 0.01\n\
+             solr_QUERY_select_requestTimes_seconds{registry=\"solr.core.films\",quantile=\"0.95\"} 0.02\n\
+             solr_QUERY_select_requestTimes_seconds{registry=\"solr.core.films\",quantile=\"0.99\"} 0.05\n\
+             solr_QUERY_select_requestTimes_seconds{registry=\"solr.core.films\",quantile=\"0.999\"} 0.09\n\
+             solr_QUERY_select_requestTimes_seconds_count{registry=\"solr.core.films\"} 42\n\
+             # TYPE solr_QUERY_select_requests_total counter\n\
+             solr_QUERY_select_requests_total{registry=\"solr.core.films\"} 42\n\
+             # TYPE solr_memory_heap_used gauge\n\
+             solr_memory_heap_used{registry=\"solr.jvm\"} 1048576\n\
+             # TYPE solr_threads_count gauge\n\
+             solr_threads_count{registry=\"solr.jvm\"} 48\n"
+        );
+    }
+}
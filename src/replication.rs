@@ -0,0 +1,283 @@
+//! Typed commands for a core's replication handler (/replication)
+//!
+//! This is for standalone leader/follower (formerly master/slave) setups, where followers poll
+//! their leader for index changes. Each command is a struct implementing
+//! `SolrReplicationCommand`, and is either built with `SolrCoreMethods::replication`, or sent in
+//! one step with `run_replication_command`. The details command is `SolrReplicationDetailsCommand`,
+//! as `SolrReplicationDetails` is the body of its response.
+//!
+//! ```no_run
+//! # use tokio::runtime::Runtime;
+//! use stellr::replication::{run_replication_command, SolrFetchIndex, SolrIndexVersion};
+//! use stellr::DirectSolrClient;
+//!
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! let leader = DirectSolrClient::new("http://solr-leader:8983/solr")?;
+//! let follower = DirectSolrClient::new("http://solr-follower:8983/solr")?;
+//!
+//! # let mut rt = Runtime::new().unwrap();
+//! # rt.block_on(async {
+//! let leader_version = run_replication_command(&leader, "films", &SolrIndexVersion).await?;
+//! let follower_version = run_replication_command(&follower, "films", &SolrIndexVersion).await?;
+//!
+//! if follower_version.generation < leader_version.generation {
+//!     let fetch = SolrFetchIndex {
+//!         leader_url: Some(String::from("http://solr-leader:8983/solr/films/replication")),
+//!         ..Default::default()
+//!     };
+//!     run_replication_command(&follower, "films", &fetch).await?;
+//! }
+//! # Ok::<(), stellr::SolrError>(())
+//! # })?;
+//! # Ok(()) }
+//! ```
+
+use serde::de::DeserializeOwned;
+
+use crate::clients::SolrCoreMethods;
+use crate::collections_admin::ParamList;
+use crate::errors::SolrResult;
+use crate::requests::SolrRequest;
+use crate::response_types::{
+    SolrIndexVersionType, SolrReplicationDetailsType, SolrReplicationStatusType,
+    SolrRestoreStatusType,
+};
+
+/// A single replication handler command, along with its parameters
+pub trait SolrReplicationCommand {
+    /// The struct solr's response is deserialised into
+    type Response: DeserializeOwned;
+
+    /// Value of the command parameter, eg. fetchindex
+    fn command(&self) -> &'static str;
+
+    /// Parameters for this command, not including the command itself
+    fn query_params(&self) -> Vec<(String, String)> {
+        Vec::new()
+    }
+}
+
+/// Send a replication command to a core and deserialise the response into its typed struct
+#[cfg(not(feature = "blocking"))]
+pub async fn run_replication_command<C, R>(
+    client: &C,
+    core: &str,
+    command: &R,
+) -> SolrResult<R::Response>
+where
+    C: SolrCoreMethods,
+    R: SolrReplicationCommand,
+{
+    client
+        .replication(core, command)?
+        .call::<R::Response>()
+        .await
+}
+
+/// Send a replication command to a core and deserialise the response into its typed struct
+#[cfg(feature = "blocking")]
+pub fn run_replication_command<C, R>(client: &C, core: &str, command: &R) -> SolrResult<R::Response>
+where
+    C: SolrCoreMethods,
+    R: SolrReplicationCommand,
+{
+    client.replication(core, command)?.call::<R::Response>()
+}
+
+/// Fetch the version and generation of the core's latest replicable commit (indexversion)
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct SolrIndexVersion;
+
+impl SolrReplicationCommand for SolrIndexVersion {
+    type Response = SolrIndexVersionType;
+
+    fn command(&self) -> &'static str {
+        "indexversion"
+    }
+}
+
+/// Fetch the replication details of a core, in its leader and/or follower role (details)
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct SolrReplicationDetailsCommand;
+
+impl SolrReplicationCommand for SolrReplicationDetailsCommand {
+    type Response = SolrReplicationDetailsType;
+
+    fn command(&self) -> &'static str {
+        "details"
+    }
+}
+
+/// Ask a follower to fetch the index from its leader now, or from another core (fetchindex)
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct SolrFetchIndex {
+    /// Replication handler URL to fetch from, instead of the configured leader (leaderUrl)
+    pub leader_url: Option<String>,
+    /// The same as leader_url, for solr versions before 8.7 (masterUrl)
+    pub master_url: Option<String>,
+    /// Wait for the fetch to finish before responding (wait)
+    pub wait: Option<bool>,
+}
+
+impl SolrReplicationCommand for SolrFetchIndex {
+    type Response = SolrReplicationStatusType;
+
+    fn command(&self) -> &'static str {
+        "fetchindex"
+    }
+
+    fn query_params(&self) -> Vec<(String, String)> {
+        ParamList::default()
+            .push_opt("leaderUrl", &self.leader_url)
+            .push_opt("masterUrl", &self.master_url)
+            .push_opt("wait", &self.wait)
+            .finish()
+    }
+}
+
+/// Stop a follower from polling its leader for changes (disablepoll)
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct SolrDisablePoll;
+
+impl SolrReplicationCommand for SolrDisablePoll {
+    type Response = SolrReplicationStatusType;
+
+    fn command(&self) -> &'static str {
+        "disablepoll"
+    }
+}
+
+/// Restart a follower's polling of its leader (enablepoll)
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct SolrEnablePoll;
+
+impl SolrReplicationCommand for SolrEnablePoll {
+    type Response = SolrReplicationStatusType;
+
+    fn command(&self) -> &'static str {
+        "enablepoll"
+    }
+}
+
+/// Stop a leader from serving its index to followers (disablereplication)
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct SolrDisableReplication;
+
+impl SolrReplicationCommand for SolrDisableReplication {
+    type Response = SolrReplicationStatusType;
+
+    fn command(&self) -> &'static str {
+        "disablereplication"
+    }
+}
+
+/// Allow a leader to serve its index to followers again (enablereplication)
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct SolrEnableReplication;
+
+impl SolrReplicationCommand for SolrEnableReplication {
+    type Response = SolrReplicationStatusType;
+
+    fn command(&self) -> &'static str {
+        "enablereplication"
+    }
+}
+
+/// Back up a core's latest commit to a snapshot directory (backup)
+///
+/// The backup runs in the background; its progress is reported in the backup section of the
+/// replication details.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct SolrReplicationBackup {
+    /// Snapshot name, giving a snapshot.<name> directory (name, defaults to a timestamp)
+    pub name: Option<String>,
+    /// Directory to write the snapshot in, instead of the data directory (location)
+    pub location: Option<String>,
+    /// Number of backups to keep, removing older ones (numberToKeep)
+    pub number_to_keep: Option<u32>,
+    /// Back up a named commit snapshot, instead of the latest commit (commitName)
+    pub commit_name: Option<String>,
+}
+
+impl SolrReplicationCommand for SolrReplicationBackup {
+    type Response = SolrReplicationStatusType;
+
+    fn command(&self) -> &'static str {
+        "backup"
+    }
+
+    fn query_params(&self) -> Vec<(String, String)> {
+        ParamList::default()
+            .push_opt("name", &self.name)
+            .push_opt("location", &self.location)
+            .push_opt("numberToKeep", &self.number_to_keep)
+            .push_opt("commitName", &self.commit_name)
+            .finish()
+    }
+}
+
+/// Restore a core's index from a snapshot (restore)
+///
+/// Restores run in the background, so follow up with `SolrRestoreStatus`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct SolrReplicationRestore {
+    /// Snapshot to restore (name, defaults to the latest)
+    pub name: Option<String>,
+    /// Directory holding the snapshot, if not the data directory (location)
+    pub location: Option<String>,
+}
+
+impl SolrReplicationCommand for SolrReplicationRestore {
+    type Response = SolrReplicationStatusType;
+
+    fn command(&self) -> &'static str {
+        "restore"
+    }
+
+    fn query_params(&self) -> Vec<(String, String)> {
+        ParamList::default()
+            .push_opt("name", &self.name)
+            .push_opt("location", &self.location)
+            .finish()
+    }
+}
+
+/// Fetch the status of the most recent restore (restorestatus)
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct SolrRestoreStatus;
+
+impl SolrReplicationCommand for SolrRestoreStatus {
+    type Response = SolrRestoreStatusType;
+
+    fn command(&self) -> &'static str {
+        "restorestatus"
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_fetch_index_params() {
+        let fetch = SolrFetchIndex {
+            master_url: Some(String::from("http://leader:8983/solr/films/replication")),
+            wait: Some(true),
+            ..Default::default()
+        };
+
+        assert_eq!(fetch.command(), "fetchindex");
+        assert_eq!(
+            fetch.query_params(),
+            vec![
+                (
+                    String::from("masterUrl"),
+                    String::from("http://leader:8983/solr/films/replication")
+                ),
+                (String::from("wait"), String::from("true")),
+            ]
+        );
+        assert!(SolrEnablePoll.query_params().is_empty());
+    }
+}
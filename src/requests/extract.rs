@@ -0,0 +1,74 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Output format for extractOnly requests (extractFormat)
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum SolrExtractFormat {
+    /// XHTML, including the structure Tika found in the document
+    Xml,
+    /// Plain text only
+    Text,
+}
+
+impl fmt::Display for SolrExtractFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolrExtractFormat::Xml => write!(f, "xml"),
+            SolrExtractFormat::Text => write!(f, "text"),
+        }
+    }
+}
+
+/// Parameters for the Solr Cell extracting request handler (/update/extract)
+///
+/// ```
+/// use stellr::SolrExtractOptions;
+///
+/// let mut extract_options = SolrExtractOptions {
+///     uprefix: Some(String::from("attr_")),
+///     ..Default::default()
+/// };
+/// extract_options.literals.insert(String::from("id"), String::from("doc-1"));
+/// extract_options.fmap.insert(String::from("content"), String::from("text"));
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct SolrExtractOptions {
+    /// Fixed field values added to the extracted document (literal.<field>)
+    pub literals: BTreeMap<String, String>,
+    /// Renames from Tika's field names to schema field names (fmap.<field>)
+    pub fmap: BTreeMap<String, String>,
+    /// Prefix for any field not present in the schema (uprefix)
+    pub uprefix: Option<String>,
+    /// Return the extracted content instead of indexing it (extractOnly)
+    pub extract_only: Option<bool>,
+    /// Format of the content returned by extractOnly (extractFormat)
+    pub extract_format: Option<SolrExtractFormat>,
+    /// Name of the uploaded file, used by Tika to detect the content type (resource.name)
+    pub resource_name: Option<String>,
+}
+
+impl SolrExtractOptions {
+    /// Build the list of query parameters for these options
+    pub(crate) fn query_params(&self) -> Vec<(String, String)> {
+        let mut params = Vec::new();
+        for (field, value) in &self.literals {
+            params.push((format!("literal.{}", field), value.clone()));
+        }
+        for (field, target) in &self.fmap {
+            params.push((format!("fmap.{}", field), target.clone()));
+        }
+        if let Some(uprefix) = &self.uprefix {
+            params.push((String::from("uprefix"), uprefix.clone()));
+        }
+        if let Some(extract_only) = self.extract_only {
+            params.push((String::from("extractOnly"), extract_only.to_string()));
+        }
+        if let Some(extract_format) = self.extract_format {
+            params.push((String::from("extractFormat"), extract_format.to_string()));
+        }
+        if let Some(resource_name) = &self.resource_name {
+            params.push((String::from("resource.name"), resource_name.clone()));
+        }
+        params
+    }
+}
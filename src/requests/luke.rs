@@ -0,0 +1,79 @@
+use std::fmt;
+
+/// Sections of a Luke response to include (show)
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum SolrLukeShow {
+    /// Fields and field types as declared in the schema, with their flags
+    Schema,
+    /// Index statistics only, without any per-field details
+    Index,
+    /// A single document's stored and indexed values, picked with id or doc_id
+    Doc,
+}
+
+impl fmt::Display for SolrLukeShow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolrLukeShow::Schema => write!(f, "schema"),
+            SolrLukeShow::Index => write!(f, "index"),
+            SolrLukeShow::Doc => write!(f, "doc"),
+        }
+    }
+}
+
+/// Parameters for the Luke request handler (/admin/luke)
+///
+/// Without show, solr returns the index statistics along with the details (flags, term counts
+/// and top terms) of each field found in the index. Luke only looks at the core it is sent to, so
+/// against a collection it reports on a single replica.
+///
+/// ```
+/// use stellr::{SolrLukeOptions, SolrLukeShow};
+///
+/// let field_details = SolrLukeOptions {
+///     fields: vec![String::from("genre"), String::from("directed_by")],
+///     num_terms: Some(5),
+///     ..Default::default()
+/// };
+/// let document = SolrLukeOptions {
+///     show: Some(SolrLukeShow::Doc),
+///     id: Some(String::from("/en/45_2006")),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct SolrLukeOptions {
+    /// Section of the response to show (show)
+    pub show: Option<SolrLukeShow>,
+    /// Limit the field details to these fields (fl)
+    pub fields: Vec<String>,
+    /// Number of top terms to list per field, where 0 skips the term statistics (numTerms)
+    pub num_terms: Option<u32>,
+    /// uniqueKey of the document to show (id)
+    pub id: Option<String>,
+    /// Lucene document number of the document to show, instead of its id (docId)
+    pub doc_id: Option<u64>,
+}
+
+impl SolrLukeOptions {
+    /// Build the list of query parameters for these options
+    pub(crate) fn query_params(&self) -> Vec<(String, String)> {
+        let mut params = Vec::new();
+        if let Some(show) = self.show {
+            params.push((String::from("show"), show.to_string()));
+        }
+        if !self.fields.is_empty() {
+            params.push((String::from("fl"), self.fields.join(",")));
+        }
+        if let Some(num_terms) = self.num_terms {
+            params.push((String::from("numTerms"), num_terms.to_string()));
+        }
+        if let Some(id) = &self.id {
+            params.push((String::from("id"), id.clone()));
+        }
+        if let Some(doc_id) = self.doc_id {
+            params.push((String::from("docId"), doc_id.to_string()));
+        }
+        params
+    }
+}
@@ -1,7 +1,28 @@
+pub mod extract;
+pub mod luke;
+pub mod nested_documents;
 pub mod solr_request;
 pub mod solr_request_builder;
+pub mod streaming_payload;
+pub mod tolerant_update;
+pub mod update_commands;
+pub mod update_formats;
 
+#[doc(inline)]
+pub use extract::{SolrExtractFormat, SolrExtractOptions};
+#[doc(inline)]
+pub use luke::{SolrLukeOptions, SolrLukeShow};
+#[doc(inline)]
+pub use nested_documents::{SolrChildTransformer, SolrLabelledDocument, SolrNestedDocument};
 #[doc(inline)]
 pub use solr_request::SolrRequest;
 #[doc(inline)]
 pub use solr_request_builder::SolrRequestBuilder;
+#[doc(inline)]
+pub use streaming_payload::PayloadFormat;
+#[doc(inline)]
+pub use tolerant_update::bisect_update;
+#[doc(inline)]
+pub use update_commands::{SolrAddOptions, SolrUpdateCommands};
+#[doc(inline)]
+pub use update_formats::SolrCsvOptions;
@@ -0,0 +1,163 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// A parent document with anonymous child documents
+///
+/// The parent's fields are flattened into the top level of the document, and the children are
+/// stored under `_childDocuments_`, which is how solr both accepts and returns anonymous children.
+/// This can be used as a payload for updates, or as the document type of a SolrSelectType when
+/// querying with a `[child]` transformer (see `SolrChildTransformer`).
+///
+/// ```
+/// use stellr::SolrNestedDocument;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, Serialize, Deserialize)]
+/// struct Product { id: String, name: String }
+/// #[derive(Debug, Serialize, Deserialize)]
+/// struct Sku { id: String, colour: String }
+///
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let product = SolrNestedDocument {
+///     parent: Product { id: String::from("p1"), name: String::from("Shirt") },
+///     children: vec![Sku { id: String::from("p1-red"), colour: String::from("red") }],
+/// };
+///
+/// assert_eq!(
+///     serde_json::to_string(&product)?,
+///     r#"{"id":"p1","name":"Shirt","_childDocuments_":[{"id":"p1-red","colour":"red"}]}"#
+/// );
+/// # Ok(()) }
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SolrNestedDocument<P, C> {
+    #[serde(flatten)]
+    pub parent: P,
+    #[serde(
+        rename = "_childDocuments_",
+        default = "Vec::new",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub children: Vec<C>,
+}
+
+/// A parent document with children stored under labelled relationship fields
+///
+/// Each entry in `children` becomes a field on the parent holding an array of child documents.
+/// Solr records the relationship in `_nest_path_`, which must be defined in the schema.
+///
+/// This type is only used for indexing. When querying, a `[child]` transformer returns the
+/// children under the same field names, so they can be deserialised into ordinary fields of a
+/// user-defined struct.
+///
+/// ```
+/// use stellr::SolrLabelledDocument;
+/// use serde_json::json;
+/// use std::collections::BTreeMap;
+///
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let mut children = BTreeMap::new();
+/// children.insert(String::from("skus"), vec![json!({"id": "p1-red"})]);
+/// let product = SolrLabelledDocument { parent: json!({"id": "p1"}), children };
+///
+/// assert_eq!(
+///     serde_json::to_string(&product)?,
+///     r#"{"id":"p1","skus":[{"id":"p1-red"}]}"#
+/// );
+/// # Ok(()) }
+/// ```
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct SolrLabelledDocument<P, C> {
+    #[serde(flatten)]
+    pub parent: P,
+    #[serde(flatten)]
+    pub children: BTreeMap<String, Vec<C>>,
+}
+
+/// Settings for the `[child]` document transformer
+///
+/// This is rendered into an fl entry by `SolrRequestBuilder::child_transformer`. All settings are
+/// optional: on solr 8+, with `_nest_path_` in the schema, parent_filter may be left out.
+///
+/// ```
+/// use stellr::SolrChildTransformer;
+///
+/// let transformer = SolrChildTransformer {
+///     child_filter: Some(String::from("colour:red")),
+///     limit: Some(5),
+///     ..Default::default()
+/// };
+/// assert_eq!(transformer.to_string(), "[child childFilter='colour:red' limit=5]");
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct SolrChildTransformer {
+    /// Query identifying all parent documents (parentFilter)
+    pub parent_filter: Option<String>,
+    /// Query restricting which children are returned (childFilter)
+    pub child_filter: Option<String>,
+    /// Maximum number of children returned per parent
+    pub limit: Option<i32>,
+    /// Fields to return for each child document
+    pub fl: Option<String>,
+}
+
+impl fmt::Display for SolrChildTransformer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[child")?;
+        if let Some(parent_filter) = &self.parent_filter {
+            write!(f, " parentFilter={}", quote_local_param(parent_filter))?;
+        }
+        if let Some(child_filter) = &self.child_filter {
+            write!(f, " childFilter={}", quote_local_param(child_filter))?;
+        }
+        if let Some(limit) = self.limit {
+            write!(f, " limit={}", limit)?;
+        }
+        if let Some(fl) = &self.fl {
+            write!(f, " fl={}", quote_local_param(fl))?;
+        }
+        write!(f, "]")
+    }
+}
+
+/// Build a query which matches a whole block of nested documents from the id of its root
+pub(crate) fn block_query(root_id: &str) -> String {
+    format!("{{!term f=_root_}}{}", root_id)
+}
+
+/// Wrap a local parameter value in single quotes, escaping as needed
+fn quote_local_param(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::{json, Value};
+
+    #[test]
+    fn anonymous_children_round_trip() {
+        let body = r#"{"id":"p1","name":"Shirt","_childDocuments_":[{"id":"p1-red"}]}"#;
+        let document: SolrNestedDocument<Value, Value> = serde_json::from_str(body).unwrap();
+
+        assert_eq!(document.parent, json!({"id": "p1", "name": "Shirt"}));
+        assert_eq!(document.children, vec![json!({"id": "p1-red"})]);
+        assert_eq!(serde_json::to_string(&document).unwrap(), body);
+    }
+
+    #[test]
+    fn quoted_transformer_params() {
+        let transformer = SolrChildTransformer {
+            parent_filter: Some(String::from("doc_type:'product'")),
+            fl: Some(String::from("id,colour")),
+            ..Default::default()
+        };
+        assert_eq!(
+            transformer.to_string(),
+            r#"[child parentFilter='doc_type:\'product\'' fl='id,colour']"#
+        );
+    }
+}
@@ -1,12 +1,32 @@
+use crate::document::SolrDocument;
+use crate::requests::nested_documents::SolrChildTransformer;
+use crate::requests::streaming_payload::{PayloadChunks, PayloadFormat};
+use crate::requests::update_commands::SolrUpdateCommands;
+use crate::requests::update_formats::xml_add_body;
 use crate::{SolrError, SolrResult};
 use reqwest::header::{HeaderMap, CONTENT_TYPE};
 use serde::Serialize;
 
 #[cfg(not(feature = "blocking"))]
-use reqwest::RequestBuilder;
+use futures::stream::{self, Stream};
+
+#[cfg(not(feature = "blocking"))]
+use reqwest::multipart::{Form, Part};
+
+#[cfg(not(feature = "blocking"))]
+use reqwest::{Body, RequestBuilder};
+
+#[cfg(feature = "blocking")]
+use crate::requests::streaming_payload::PayloadReader;
 
 #[cfg(feature = "blocking")]
-use reqwest::blocking::RequestBuilder;
+use reqwest::blocking::multipart::{Form, Part};
+
+#[cfg(feature = "blocking")]
+use reqwest::blocking::{Body, RequestBuilder};
+
+#[cfg(feature = "blocking")]
+use std::io::Read;
 
 /// Helper methods to simplify request building
 ///
@@ -16,18 +36,61 @@ use reqwest::blocking::RequestBuilder;
 /// Raw reqwest::RequestBuilder methods may also be used if a suitable helper method is not found
 /// here.
 pub trait SolrRequestBuilder {
+    fn child_transformer(self, transformer: &SolrChildTransformer) -> RequestBuilder;
+    fn commands(self, update_commands: &SolrUpdateCommands) -> SolrResult<RequestBuilder>;
     fn commit(self) -> RequestBuilder;
     fn content_type(self, content_type: &str) -> RequestBuilder;
     fn debug_query(self, debug: bool) -> RequestBuilder;
     fn fl(self, field_list: &str) -> RequestBuilder;
+    fn fl_for<T: SolrDocument>(self) -> RequestBuilder;
     fn fq(self, filter_query: &str) -> RequestBuilder;
+    fn max_errors(self, max_errors: i32) -> RequestBuilder;
+    fn multipart_file(self, file_name: &str, contents: Vec<u8>) -> RequestBuilder;
+    #[cfg(not(feature = "blocking"))]
+    fn multipart_stream(self, file_name: &str, contents: impl Into<Body>) -> RequestBuilder;
+    #[cfg(feature = "blocking")]
+    fn multipart_reader(
+        self,
+        file_name: &str,
+        contents: impl Read + Send + 'static,
+    ) -> RequestBuilder;
     fn q(self, query: &str) -> RequestBuilder;
     fn rows(self, row_count: u32) -> RequestBuilder;
+    fn update_chain(self, chain: &str) -> RequestBuilder;
+    fn use_params(self, param_sets: &str) -> RequestBuilder;
     fn wt(self, format: &str) -> RequestBuilder;
     fn payload(self, serializable_payload: &impl Serialize) -> SolrResult<RequestBuilder>;
+    fn payload_iter<I>(self, documents: I, format: PayloadFormat) -> RequestBuilder
+    where
+        I: IntoIterator,
+        I::IntoIter: Send + Sync + 'static,
+        I::Item: Serialize;
+    #[cfg(not(feature = "blocking"))]
+    fn payload_stream<S>(self, documents: S, format: PayloadFormat) -> RequestBuilder
+    where
+        S: Stream + Send + Sync + 'static,
+        S::Item: Serialize;
+    fn xml_payload(self, serializable_payload: &impl Serialize) -> SolrResult<RequestBuilder>;
 }
 
 impl SolrRequestBuilder for RequestBuilder {
+    /// Return nested child documents using the `[child]` transformer
+    ///
+    /// This adds an extra fl entry for the transformer, so should be combined with `fl` to select
+    /// the parent fields (eg. `.fl("*")`).
+    fn child_transformer(self, transformer: &SolrChildTransformer) -> RequestBuilder {
+        self.query(&[("fl", transformer.to_string())])
+    }
+
+    /// Use a set of ordered update commands (add, delete, commit, optimize) as the request body
+    ///
+    /// This is an alternative to `payload` for update requests, for when documents need
+    /// per-document options or to be mixed with deletes in a single request.
+    fn commands(self, update_commands: &SolrUpdateCommands) -> SolrResult<RequestBuilder> {
+        let json_payload = serde_json::to_string(update_commands)?;
+        Ok(self.body(json_payload))
+    }
+
     /// Mark the query to force a commit
     fn commit(self) -> RequestBuilder {
         self.query(&[("commit", "true")])
@@ -54,11 +117,55 @@ impl SolrRequestBuilder for RequestBuilder {
         self.query(&[("fl", field_list)])
     }
 
+    /// limit fields returned (fl) by the request to those held by a SolrDocument type
+    fn fl_for<T: SolrDocument>(self) -> RequestBuilder {
+        self.fl(&T::fl())
+    }
+
     /// Applies a filter query (fq) to the request
     fn fq(self, filter_query: &str) -> RequestBuilder {
         self.query(&[("fq", filter_query)])
     }
 
+    /// Sets the number of failed documents tolerated by an update (maxErrors)
+    ///
+    /// This relies on the TolerantUpdateProcessor being present in the update chain, and a value
+    /// of -1 tolerates any number of errors. Rejected documents are reported back in the errors
+    /// list of the SolrUpdateType response.
+    fn max_errors(self, max_errors: i32) -> RequestBuilder {
+        self.query(&[("maxErrors", &max_errors.to_string())])
+    }
+
+    /// Upload a file as a multipart form body, eg. for `SolrCloudMethods::extract`
+    fn multipart_file(self, file_name: &str, contents: Vec<u8>) -> RequestBuilder {
+        let file_part = Part::bytes(contents).file_name(file_name.to_string());
+        self.multipart(Form::new().part("file", file_part))
+    }
+
+    /// Upload a file as a multipart form body, reading the contents from a reqwest::Body
+    ///
+    /// This allows large files to be streamed (eg. with `Body::wrap_stream`) rather than held in
+    /// memory.
+    #[cfg(not(feature = "blocking"))]
+    fn multipart_stream(self, file_name: &str, contents: impl Into<Body>) -> RequestBuilder {
+        let file_part = Part::stream(contents).file_name(file_name.to_string());
+        self.multipart(Form::new().part("file", file_part))
+    }
+
+    /// Upload a file as a multipart form body, reading the contents from a std::io::Read
+    ///
+    /// This allows large files to be streamed (eg. from a std::fs::File) rather than held in
+    /// memory.
+    #[cfg(feature = "blocking")]
+    fn multipart_reader(
+        self,
+        file_name: &str,
+        contents: impl Read + Send + 'static,
+    ) -> RequestBuilder {
+        let file_part = Part::reader(contents).file_name(file_name.to_string());
+        self.multipart(Form::new().part("file", file_part))
+    }
+
     /// Applies a query (q) to the request
     fn q(self, query: &str) -> RequestBuilder {
         self.query(&[("q", query)])
@@ -69,6 +176,19 @@ impl SolrRequestBuilder for RequestBuilder {
         self.query(&[("rows", &row_count.to_string())])
     }
 
+    /// Selects a named update processor chain (update.chain) for this request
+    fn update_chain(self, chain: &str) -> RequestBuilder {
+        self.query(&[("update.chain", chain)])
+    }
+
+    /// Applies one or more param sets (useParams), defined with the Request Parameters API
+    ///
+    /// Several sets can be given as a comma separated list, with later sets overriding earlier
+    /// ones. Parameters set directly on the request still take precedence.
+    fn use_params(self, param_sets: &str) -> RequestBuilder {
+        self.query(&[("useParams", param_sets)])
+    }
+
     /// Specifies the response format
     ///
     /// NB. This crate assumes that the output response is in json format, so you should only use
@@ -93,4 +213,67 @@ impl SolrRequestBuilder for RequestBuilder {
             Err(SolrError::PayloadNotAJsonArrayError)
         }
     }
+
+    /// Lazily serialize documents from an iterator into a chunked request body
+    ///
+    /// Unlike `payload`, the documents are only serialized as the body is sent, so memory use
+    /// stays flat regardless of the number of documents. Use `PayloadFormat::JsonArray` with
+    /// `SolrCloudMethods::update`, or `PayloadFormat::JsonLines` with
+    /// `SolrCloudMethods::update_json_docs`.
+    ///
+    /// Serialization errors abort the request part way through, and surface as a
+    /// SolrError::HTTPError when the request is sent.
+    #[cfg(not(feature = "blocking"))]
+    fn payload_iter<I>(self, documents: I, format: PayloadFormat) -> RequestBuilder
+    where
+        I: IntoIterator,
+        I::IntoIter: Send + Sync + 'static,
+        I::Item: Serialize,
+    {
+        let chunks = PayloadChunks::new(documents.into_iter(), format);
+        self.body(Body::wrap_stream(stream::iter(chunks)))
+    }
+
+    /// Lazily serialize documents from an iterator into a chunked request body
+    ///
+    /// Unlike `payload`, the documents are only serialized as the body is sent, so memory use
+    /// stays flat regardless of the number of documents. Use `PayloadFormat::JsonArray` with
+    /// `SolrCloudMethods::update`, or `PayloadFormat::JsonLines` with
+    /// `SolrCloudMethods::update_json_docs`.
+    ///
+    /// Serialization errors abort the request part way through, and surface as a
+    /// SolrError::HTTPError when the request is sent.
+    #[cfg(feature = "blocking")]
+    fn payload_iter<I>(self, documents: I, format: PayloadFormat) -> RequestBuilder
+    where
+        I: IntoIterator,
+        I::IntoIter: Send + Sync + 'static,
+        I::Item: Serialize,
+    {
+        let chunks = PayloadChunks::new(documents.into_iter(), format);
+        self.body(Body::new(PayloadReader::new(chunks)))
+    }
+
+    /// Lazily serialize documents from a futures Stream into a chunked request body
+    ///
+    /// This behaves as `payload_iter`, but pulls documents from an async source instead.
+    #[cfg(not(feature = "blocking"))]
+    fn payload_stream<S>(self, documents: S, format: PayloadFormat) -> RequestBuilder
+    where
+        S: Stream + Send + Sync + 'static,
+        S::Item: Serialize,
+    {
+        let chunks = crate::requests::streaming_payload::payload_stream(documents, format);
+        self.body(Body::wrap_stream(chunks))
+    }
+
+    /// Serialize the supplied list of objects to a solr XML `<add>` command, and use that for the
+    /// request body
+    ///
+    /// As with `payload`, the serializable_payload should be an array or Vec of instances
+    /// implementing the Serialize trait. This is intended for use with
+    /// `SolrCloudMethods::update_xml`.
+    fn xml_payload(self, serializable_payload: &impl Serialize) -> SolrResult<RequestBuilder> {
+        Ok(self.body(xml_add_body(serializable_payload)?))
+    }
 }
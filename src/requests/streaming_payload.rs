@@ -0,0 +1,207 @@
+use serde::Serialize;
+
+#[cfg(not(feature = "blocking"))]
+use futures::stream::{self, Stream, StreamExt};
+
+#[cfg(feature = "blocking")]
+use std::io::{self, Read};
+
+/// Size at which serialised documents are flushed into the request body as a chunk
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Layout of a streamed update body
+///
+/// `JsonArray` matches the body produced by `SolrRequestBuilder::payload`, and can be sent to any
+/// JSON update handler. `JsonLines` writes one document per line, and is intended for the
+/// `/update/json/docs` handler (see `SolrCloudMethods::update_json_docs`).
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum PayloadFormat {
+    /// A single JSON array, eg. `[{"id":"1"},{"id":"2"}]`
+    JsonArray,
+    /// Newline separated JSON documents
+    JsonLines,
+}
+
+impl PayloadFormat {
+    fn opening(self) -> &'static [u8] {
+        match self {
+            PayloadFormat::JsonArray => b"[",
+            PayloadFormat::JsonLines => b"",
+        }
+    }
+
+    fn separator(self) -> &'static [u8] {
+        match self {
+            PayloadFormat::JsonArray => b",",
+            PayloadFormat::JsonLines => b"\n",
+        }
+    }
+
+    fn closing(self) -> &'static [u8] {
+        match self {
+            PayloadFormat::JsonArray => b"]",
+            PayloadFormat::JsonLines => b"\n",
+        }
+    }
+}
+
+/// Lazily serialise an iterator of documents into body chunks of roughly CHUNK_SIZE bytes
+pub(crate) struct PayloadChunks<I> {
+    documents: I,
+    format: PayloadFormat,
+    opened: bool,
+    written: bool,
+    finished: bool,
+}
+
+impl<I> PayloadChunks<I> {
+    pub(crate) fn new(documents: I, format: PayloadFormat) -> PayloadChunks<I> {
+        PayloadChunks {
+            documents,
+            format,
+            opened: false,
+            written: false,
+            finished: false,
+        }
+    }
+}
+
+impl<I> Iterator for PayloadChunks<I>
+where
+    I: Iterator,
+    I::Item: Serialize,
+{
+    type Item = Result<Vec<u8>, serde_json::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let mut chunk = Vec::with_capacity(CHUNK_SIZE);
+        if !self.opened {
+            chunk.extend_from_slice(self.format.opening());
+            self.opened = true;
+        }
+
+        while chunk.len() < CHUNK_SIZE {
+            match self.documents.next() {
+                Some(document) => {
+                    if self.written {
+                        chunk.extend_from_slice(self.format.separator());
+                    }
+                    if let Err(e) = serde_json::to_writer(&mut chunk, &document) {
+                        self.finished = true;
+                        return Some(Err(e));
+                    }
+                    self.written = true;
+                }
+                None => {
+                    chunk.extend_from_slice(self.format.closing());
+                    self.finished = true;
+                    break;
+                }
+            }
+        }
+
+        Some(Ok(chunk))
+    }
+}
+
+/// Lazily serialise a stream of documents into body chunks, one per document
+#[cfg(not(feature = "blocking"))]
+pub(crate) fn payload_stream<S>(
+    documents: S,
+    format: PayloadFormat,
+) -> impl Stream<Item = Result<Vec<u8>, serde_json::Error>> + Send + Sync + 'static
+where
+    S: Stream + Send + Sync + 'static,
+    S::Item: Serialize,
+{
+    let encoded = documents.enumerate().map(move |(index, document)| {
+        let mut chunk = Vec::new();
+        if index > 0 {
+            chunk.extend_from_slice(format.separator());
+        }
+        serde_json::to_writer(&mut chunk, &document).map(|_| chunk)
+    });
+
+    stream::once(async move { Ok(format.opening().to_vec()) })
+        .chain(encoded)
+        .chain(stream::once(async move { Ok(format.closing().to_vec()) }))
+}
+
+/// Adapts PayloadChunks to std::io::Read, for use as a blocking request body
+#[cfg(feature = "blocking")]
+pub(crate) struct PayloadReader<I> {
+    chunks: PayloadChunks<I>,
+    current: Vec<u8>,
+    position: usize,
+}
+
+#[cfg(feature = "blocking")]
+impl<I> PayloadReader<I> {
+    pub(crate) fn new(chunks: PayloadChunks<I>) -> PayloadReader<I> {
+        PayloadReader {
+            chunks,
+            current: Vec::new(),
+            position: 0,
+        }
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl<I> Read for PayloadReader<I>
+where
+    I: Iterator,
+    I::Item: Serialize,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.position >= self.current.len() {
+            match self.chunks.next() {
+                Some(Ok(chunk)) => {
+                    self.current = chunk;
+                    self.position = 0;
+                }
+                Some(Err(e)) => return Err(io::Error::new(io::ErrorKind::InvalidData, e)),
+                None => return Ok(0),
+            }
+        }
+
+        let remaining = &self.current[self.position..];
+        let length = remaining.len().min(buf.len());
+        buf[..length].copy_from_slice(&remaining[..length]);
+        self.position += length;
+        Ok(length)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    fn collect_body(documents: Vec<serde_json::Value>, format: PayloadFormat) -> String {
+        let chunks: Result<Vec<_>, _> = PayloadChunks::new(documents.into_iter(), format).collect();
+        String::from_utf8(chunks.unwrap().concat()).unwrap()
+    }
+
+    #[test]
+    fn json_array_chunks() {
+        let documents = vec![json!({"id": "1"}), json!({"id": "2"})];
+        assert_eq!(
+            collect_body(documents, PayloadFormat::JsonArray),
+            r#"[{"id":"1"},{"id":"2"}]"#
+        );
+        assert_eq!(collect_body(vec![], PayloadFormat::JsonArray), "[]");
+    }
+
+    #[test]
+    fn json_lines_chunks() {
+        let documents = vec![json!({"id": "1"}), json!({"id": "2"})];
+        assert_eq!(
+            collect_body(documents, PayloadFormat::JsonLines),
+            "{\"id\":\"1\"}\n{\"id\":\"2\"}\n"
+        );
+    }
+}
@@ -0,0 +1,165 @@
+use serde::Serialize;
+
+use crate::clients::SolrCloudMethods;
+use crate::errors::SolrResult;
+use crate::requests::{SolrRequest, SolrRequestBuilder};
+use crate::response_types::SolrUpdateType;
+
+use tracing::{debug, warn};
+
+/// Send a batch of documents, bisecting on failure to isolate any documents solr rejects
+///
+/// The whole batch is sent as a single update first. If solr rejects it (ie. a non-zero status in
+/// the responseHeader), the batch is split in half and each half is retried, until the documents
+/// which cannot be indexed have been narrowed down to single document updates. Each of these is
+/// handed to the dead_letter callback, along with the response solr gave for it.
+///
+/// The returned value is the number of documents handed to dead_letter. No commit is sent, so
+/// this should be followed with a commit (or rely on autoCommit) as usual.
+///
+/// ```no_run
+/// # use tokio::runtime::Runtime;
+/// use stellr::prelude::*;
+/// use stellr::{bisect_update, DirectSolrClient};
+/// use serde_json::{json, Value};
+///
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let solr_client = DirectSolrClient::new("http://localhost:8983/solr")?;
+/// let my_data = vec![json!({"id": "good"}), json!({"id": "bad", "initial_release_date": "?"})];
+///
+/// let mut rejected: Vec<Value> = Vec::new();
+/// # let mut rt = Runtime::new().unwrap();
+/// let rejected_count =
+/// # rt.block_on(async {
+///     bisect_update(&solr_client, "films", &my_data, |doc, _| rejected.push(doc.clone()))
+///     .await
+/// # })
+///     ?;
+///
+/// assert_eq!(rejected_count, 1);
+/// # Ok(()) }
+/// ```
+///
+/// ## Errors
+///
+/// Failures to reach solr, or to parse its response, are returned immediately rather than being
+/// treated as a rejected batch.
+#[cfg(not(feature = "blocking"))]
+pub async fn bisect_update<C, T, F>(
+    client: &C,
+    collection: &str,
+    documents: &[T],
+    mut dead_letter: F,
+) -> SolrResult<usize>
+where
+    C: SolrCloudMethods + ?Sized,
+    T: Serialize,
+    F: FnMut(&T, &SolrUpdateType),
+{
+    let mut rejected = 0;
+    let mut pending = vec![(0, documents.len())];
+
+    while let Some((start, end)) = pending.pop() {
+        let batch = &documents[start..end];
+        if batch.is_empty() {
+            continue;
+        }
+
+        let response = client
+            .update(collection)?
+            .payload(&batch)?
+            .call::<SolrUpdateType>()
+            .await?;
+
+        rejected +=
+            handle_batch_response(&mut pending, start, end, batch, &response, &mut dead_letter);
+    }
+
+    Ok(rejected)
+}
+
+/// Send a batch of documents, bisecting on failure to isolate any documents solr rejects
+///
+/// The whole batch is sent as a single update first. If solr rejects it (ie. a non-zero status in
+/// the responseHeader), the batch is split in half and each half is retried, until the documents
+/// which cannot be indexed have been narrowed down to single document updates. Each of these is
+/// handed to the dead_letter callback, along with the response solr gave for it.
+///
+/// The returned value is the number of documents handed to dead_letter. No commit is sent, so
+/// this should be followed with a commit (or rely on autoCommit) as usual.
+///
+/// ## Errors
+///
+/// Failures to reach solr, or to parse its response, are returned immediately rather than being
+/// treated as a rejected batch.
+#[cfg(feature = "blocking")]
+pub fn bisect_update<C, T, F>(
+    client: &C,
+    collection: &str,
+    documents: &[T],
+    mut dead_letter: F,
+) -> SolrResult<usize>
+where
+    C: SolrCloudMethods + ?Sized,
+    T: Serialize,
+    F: FnMut(&T, &SolrUpdateType),
+{
+    let mut rejected = 0;
+    let mut pending = vec![(0, documents.len())];
+
+    while let Some((start, end)) = pending.pop() {
+        let batch = &documents[start..end];
+        if batch.is_empty() {
+            continue;
+        }
+
+        let response = client
+            .update(collection)?
+            .payload(&batch)?
+            .call::<SolrUpdateType>()?;
+
+        rejected +=
+            handle_batch_response(&mut pending, start, end, batch, &response, &mut dead_letter);
+    }
+
+    Ok(rejected)
+}
+
+/// Queue up both halves of a rejected batch, or dead-letter it if it is down to one document
+///
+/// Returns the number of documents dead-lettered (zero or one).
+fn handle_batch_response<T, F>(
+    pending: &mut Vec<(usize, usize)>,
+    start: usize,
+    end: usize,
+    batch: &[T],
+    response: &SolrUpdateType,
+    dead_letter: &mut F,
+) -> usize
+where
+    F: FnMut(&T, &SolrUpdateType),
+{
+    if response.responseHeader.status == 0 {
+        return 0;
+    }
+
+    if batch.len() == 1 {
+        warn!(
+            "Document {} rejected by solr, passing to dead letter handler",
+            start
+        );
+        dead_letter(&batch[0], response);
+        1
+    } else {
+        let middle = start + batch.len() / 2;
+        debug!(
+            "Batch {}..{} rejected by solr, bisecting at {}",
+            start, end, middle
+        );
+        // the stack is popped from the end, so push the right half first to keep document order
+        pending.push((middle, end));
+        pending.push((start, middle));
+        0
+    }
+}
@@ -0,0 +1,234 @@
+use serde::ser::{SerializeMap, Serializer};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::fmt;
+
+use crate::errors::SolrResult;
+use crate::requests::nested_documents::block_query;
+
+/// Per-document options for an add command
+///
+/// ```
+/// use stellr::{SolrAddOptions, SolrUpdateCommands};
+/// use serde_json::json;
+///
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let options = SolrAddOptions { overwrite: Some(false), ..Default::default() };
+/// let commands = SolrUpdateCommands::new()
+///     .add_document_with_options(&json!({"id": "1"}), options)?;
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct SolrAddOptions {
+    /// Set to false to skip the uniqueKey check, and allow duplicate documents
+    pub overwrite: Option<bool>,
+    /// Commit this document within the given number of milliseconds
+    pub commit_within: Option<u32>,
+}
+
+/// Builder for a JSON update body holding several commands
+///
+/// Solr's JSON update handler accepts a single object with repeated add, delete, commit and
+/// optimize keys, which are applied in the order they appear. This builder keeps track of that
+/// ordering, and is sent using `SolrRequestBuilder::commands` on an update request.
+///
+/// ```no_run
+/// # use tokio::runtime::Runtime;
+/// use stellr::prelude::*;
+/// use stellr::{DirectSolrClient, SolrAddOptions, SolrUpdateCommands};
+/// use stellr::response_types::SolrUpdateType;
+/// use serde_json::json;
+///
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let commands = SolrUpdateCommands::new()
+///     .delete_by_query("genre:Thriller")
+///     .add_document(&json!({"id": "new-film", "genre": "Thriller"}))?
+///     .add_document_with_options(
+///         &json!({"id": "duplicate-film"}),
+///         SolrAddOptions { overwrite: Some(false), commit_within: Some(5000) },
+///     )?
+///     .commit();
+///
+/// let solr_client = DirectSolrClient::new("http://localhost:8983/solr")?;
+/// let solr_request = solr_client
+///     .update("films")?
+///     .commands(&commands)?;
+///
+/// # let mut rt = Runtime::new().unwrap();
+/// let result_struct =
+/// # rt.block_on(async {
+///     solr_request
+///         .call::<SolrUpdateType>()
+///         .await
+/// # })
+///     ?;
+///
+/// assert_eq!(result_struct.responseHeader.status, 0);
+/// # Ok(()) }
+/// ```
+#[derive(Serialize, Clone, Debug, Default, PartialEq)]
+#[serde(transparent)]
+pub struct SolrUpdateCommands {
+    commands: SolrCommandList,
+}
+
+impl SolrUpdateCommands {
+    pub fn new() -> SolrUpdateCommands {
+        SolrUpdateCommands::default()
+    }
+
+    /// Add a single document
+    pub fn add_document(self, document: &impl Serialize) -> SolrResult<SolrUpdateCommands> {
+        self.add_document_with_options(document, SolrAddOptions::default())
+    }
+
+    /// Add a single document, with overwrite and commitWithin options
+    pub fn add_document_with_options(
+        self,
+        document: &impl Serialize,
+        options: SolrAddOptions,
+    ) -> SolrResult<SolrUpdateCommands> {
+        let mut command = Map::new();
+        command.insert(String::from("doc"), serde_json::to_value(document)?);
+        if let Some(overwrite) = options.overwrite {
+            command.insert(String::from("overwrite"), Value::from(overwrite));
+        }
+        if let Some(commit_within) = options.commit_within {
+            command.insert(String::from("commitWithin"), Value::from(commit_within));
+        }
+        Ok(self.push("add", Value::Object(command)))
+    }
+
+    /// Delete a document by its uniqueKey
+    pub fn delete_by_id(self, id: &str) -> SolrUpdateCommands {
+        self.push("delete", json!({ "id": id }))
+    }
+
+    /// Delete several documents by their uniqueKeys
+    pub fn delete_by_ids(self, ids: &[&str]) -> SolrUpdateCommands {
+        self.push("delete", json!(ids))
+    }
+
+    /// Delete all documents matching a query
+    pub fn delete_by_query(self, query: &str) -> SolrUpdateCommands {
+        self.push("delete", json!({ "query": query }))
+    }
+
+    /// Delete a whole block of nested documents (the root and all its descendants) by root id
+    pub fn delete_block(self, root_id: &str) -> SolrUpdateCommands {
+        self.delete_by_query(&block_query(root_id))
+    }
+
+    /// Commit all preceding commands
+    pub fn commit(self) -> SolrUpdateCommands {
+        self.push("commit", json!({}))
+    }
+
+    /// Optimize the index, after all preceding commands
+    pub fn optimize(self) -> SolrUpdateCommands {
+        self.push("optimize", json!({}))
+    }
+
+    /// Number of commands in this body
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    /// Check whether any commands have been added yet
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    fn push(mut self, command: &'static str, value: Value) -> SolrUpdateCommands {
+        self.commands.push(command, value);
+        self
+    }
+}
+
+impl fmt::Display for SolrUpdateCommands {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.commands.fmt(f)
+    }
+}
+
+/// An ordered list of commands, for solr's JSON endpoints which accept repeated command keys
+///
+/// The JSON update handler, and the Schema, Config and Security APIs, each take a single object
+/// whose keys may repeat (eg. several add-field commands), applied in the order they appear. This
+/// serializes to that object, repeating keys as needed to preserve command order.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct SolrCommandList {
+    commands: Vec<(&'static str, Value)>,
+}
+
+impl SolrCommandList {
+    pub(crate) fn push(&mut self, command: &'static str, value: Value) {
+        self.commands.push((command, value));
+    }
+
+    /// Push a command whose body is a definition struct (eg. a schema field)
+    pub(crate) fn push_serialized(
+        &mut self,
+        command: &'static str,
+        definition: &impl Serialize,
+    ) -> SolrResult<()> {
+        self.push(command, serde_json::to_value(definition)?);
+        Ok(())
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+}
+
+impl Serialize for SolrCommandList {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.commands.len()))?;
+        for (command, value) in &self.commands {
+            map.serialize_entry(command, value)?;
+        }
+        map.end()
+    }
+}
+
+impl fmt::Display for SolrCommandList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match serde_json::to_string(self) {
+            Ok(body) => write!(f, "{}", body),
+            Err(_) => Err(fmt::Error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn command_order_and_repeats() {
+        let commands = SolrUpdateCommands::new()
+            .add_document(&json!({"id": "1"}))
+            .unwrap()
+            .add_document_with_options(
+                &json!({"id": "2"}),
+                SolrAddOptions {
+                    overwrite: Some(false),
+                    commit_within: Some(1000),
+                },
+            )
+            .unwrap()
+            .delete_by_id("3")
+            .commit();
+
+        assert_eq!(
+            commands.to_string(),
+            r#"{"add":{"doc":{"id":"1"}},"add":{"commitWithin":1000,"doc":{"id":"2"},"overwrite":false},"delete":{"id":"3"},"commit":{}}"#
+        );
+    }
+}
@@ -0,0 +1,176 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+use crate::{SolrError, SolrResult};
+
+/// Parameters for the CSV update handler (/update/csv)
+///
+/// Any settings left as None are not sent, so solr's defaults apply (comma separated, with a
+/// header row and double-quote encapsulation).
+///
+/// ```
+/// use stellr::SolrCsvOptions;
+///
+/// let mut csv_options = SolrCsvOptions {
+///     separator: Some('\t'),
+///     header: Some(false),
+///     fieldnames: Some(vec![String::from("id"), String::from("genre")]),
+///     ..Default::default()
+/// };
+/// // split the genre column on pipes into a multi-valued field
+/// csv_options.field_splits.insert(String::from("genre"), '|');
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct SolrCsvOptions {
+    /// Character separating values within a row (separator)
+    pub separator: Option<char>,
+    /// Whether the first row holds the field names (header)
+    pub header: Option<bool>,
+    /// Field names for each column, overriding or replacing the header (fieldnames)
+    pub fieldnames: Option<Vec<String>>,
+    /// Split every value into multiple values, using the separator (split)
+    pub split: Option<bool>,
+    /// Character used to quote values (encapsulator)
+    pub encapsulator: Option<char>,
+    /// Fields to split into multiple values, each with its own separator (f.<field>.split)
+    pub field_splits: BTreeMap<String, char>,
+}
+
+impl SolrCsvOptions {
+    /// Build the list of query parameters for these options
+    pub(crate) fn query_params(&self) -> Vec<(String, String)> {
+        let mut params = Vec::new();
+        if let Some(separator) = self.separator {
+            params.push((String::from("separator"), separator.to_string()));
+        }
+        if let Some(header) = self.header {
+            params.push((String::from("header"), header.to_string()));
+        }
+        if let Some(fieldnames) = &self.fieldnames {
+            params.push((String::from("fieldnames"), fieldnames.join(",")));
+        }
+        if let Some(split) = self.split {
+            params.push((String::from("split"), split.to_string()));
+        }
+        if let Some(encapsulator) = self.encapsulator {
+            params.push((String::from("encapsulator"), encapsulator.to_string()));
+        }
+        for (field, separator) in &self.field_splits {
+            params.push((format!("f.{}.split", field), String::from("true")));
+            params.push((format!("f.{}.separator", field), separator.to_string()));
+        }
+        params
+    }
+}
+
+/// Serialize a list of documents into a solr XML `<add>` command
+///
+/// Array values become repeated fields, null values are left out, and documents nested under
+/// `_childDocuments_` or under other fields are written as child `<doc>` elements.
+pub(crate) fn xml_add_body(serializable_payload: &impl Serialize) -> SolrResult<String> {
+    let documents = match serde_json::to_value(serializable_payload)? {
+        Value::Array(documents) => documents,
+        _ => return Err(SolrError::PayloadNotAJsonArrayError),
+    };
+
+    let mut body = String::from("<add>");
+    for document in &documents {
+        write_xml_doc(&mut body, document)?;
+    }
+    body.push_str("</add>");
+    Ok(body)
+}
+
+fn write_xml_doc(body: &mut String, document: &Value) -> SolrResult<()> {
+    let fields = match document {
+        Value::Object(fields) => fields,
+        _ => return Err(SolrError::PayloadNotAJsonArrayError),
+    };
+
+    body.push_str("<doc>");
+    for (name, value) in fields {
+        if name == "_childDocuments_" {
+            for child in value.as_array().into_iter().flatten() {
+                write_xml_doc(body, child)?;
+            }
+        } else {
+            write_xml_field(body, name, value)?;
+        }
+    }
+    body.push_str("</doc>");
+    Ok(())
+}
+
+fn write_xml_field(body: &mut String, name: &str, value: &Value) -> SolrResult<()> {
+    match value {
+        Value::Null => {}
+        Value::Array(values) => {
+            for value in values {
+                write_xml_field(body, name, value)?;
+            }
+        }
+        Value::Object(_) => {
+            body.push_str(&format!("<field name=\"{}\">", escape_xml(name)));
+            write_xml_doc(body, value)?;
+            body.push_str("</field>");
+        }
+        Value::String(text) => body.push_str(&format!(
+            "<field name=\"{}\">{}</field>",
+            escape_xml(name),
+            escape_xml(text)
+        )),
+        other => body.push_str(&format!(
+            "<field name=\"{}\">{}</field>",
+            escape_xml(name),
+            other
+        )),
+    }
+    Ok(())
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn xml_documents() {
+        let documents = json!([
+            {"id": "1", "genre": ["Drama", "Crime & Thriller"], "rank": 3, "missing": null},
+            {"id": "2", "_childDocuments_": [{"id": "2-a"}]}
+        ]);
+        assert_eq!(
+            xml_add_body(&documents).unwrap(),
+            "<add><doc><field name=\"genre\">Drama</field>\
+             <field name=\"genre\">Crime &amp; Thriller</field>\
+             <field name=\"id\">1</field><field name=\"rank\">3</field></doc>\
+             <doc><doc><field name=\"id\">2-a</field></doc><field name=\"id\">2</field></doc></add>"
+        );
+    }
+
+    #[test]
+    fn csv_params() {
+        let mut csv_options = SolrCsvOptions {
+            header: Some(true),
+            ..Default::default()
+        };
+        csv_options.field_splits.insert(String::from("genre"), '|');
+        assert_eq!(
+            csv_options.query_params(),
+            vec![
+                (String::from("header"), String::from("true")),
+                (String::from("f.genre.split"), String::from("true")),
+                (String::from("f.genre.separator"), String::from("|")),
+            ]
+        );
+    }
+}
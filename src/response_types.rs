@@ -6,10 +6,15 @@
 //! composed into other types (eg. in SolrSelectType and SolrUpdateType).
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
 use std::fmt;
 use std::fmt::{Debug, Display};
 
+use crate::schema::SolrSchema;
+use crate::security::{SolrAuthenticationConfig, SolrAuthorizationConfig};
+
 //
 // Generic/Common Structs
 //
@@ -24,6 +29,8 @@ pub struct SolrResponseHeader {
     pub params: Option<HashMap<String, String>>,
     pub rf: Option<u32>,
     pub zkConnected: Option<bool>,
+    pub errors: Option<Vec<SolrTolerantError>>,
+    pub maxErrors: Option<i32>,
 }
 
 impl fmt::Display for SolrResponseHeader {
@@ -32,6 +39,41 @@ impl fmt::Display for SolrResponseHeader {
     }
 }
 
+/// Struct to match a single entry in the errors list of a TolerantUpdateProcessor response
+///
+/// Solr reports these within the responseHeader when an update is sent with maxErrors, one entry
+/// per rejected command.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrTolerantError {
+    #[serde(rename = "type")]
+    pub error_type: String,
+    pub id: String,
+    pub message: String,
+}
+
+impl fmt::Display for SolrTolerantError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}: {}", self.error_type, self.id, self.message)
+    }
+}
+
+/// Struct to match the error body solr returns alongside a failed request
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrErrorBody {
+    pub msg: Option<String>,
+    pub code: u32,
+    pub metadata: Option<Vec<String>>,
+    pub trace: Option<String>,
+    /// Per-command failures, eg. from a rejected Schema API request
+    pub details: Option<Vec<Value>>,
+}
+
+impl fmt::Display for SolrErrorBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+
 /// Struct to match the standard solr select body
 ///
 /// This does not specify the actual structure of the returned documents, hence the need to
@@ -82,12 +124,28 @@ where
 }
 
 /// Standard structure for an update response
+///
+/// When the update was sent with maxErrors, any documents rejected by solr's
+/// TolerantUpdateProcessor are listed in the responseHeader, and can be read with `errors()`.
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
 pub struct SolrUpdateType {
     pub responseHeader: SolrResponseHeader,
+    pub error: Option<SolrErrorBody>,
     pub debug: Option<String>,
 }
 
+impl SolrUpdateType {
+    /// List the per-document errors tolerated by solr during this update
+    pub fn errors(&self) -> &[SolrTolerantError] {
+        self.responseHeader.errors.as_deref().unwrap_or(&[])
+    }
+
+    /// Check whether solr accepted the whole update without tolerating any errors
+    pub fn is_clean(&self) -> bool {
+        self.responseHeader.status == 0 && self.errors().is_empty()
+    }
+}
+
 impl fmt::Display for SolrUpdateType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{:#?}", self)
@@ -107,3 +165,1416 @@ impl fmt::Display for SolrListCollectionsType {
         write!(f, "{:#?}", self)
     }
 }
+
+/// Standard structure for most calls to /admin/collections (eg. CREATE, DELETE, SPLITSHARD)
+///
+/// Solr reports per-node outcomes under success and failure, whose shape varies between actions
+/// (eg. a map of node name to core response, or a plain message), so these are left untyped.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrCollectionsAdminType {
+    pub responseHeader: SolrResponseHeader,
+    pub success: Option<Value>,
+    pub failure: Option<Value>,
+    pub warning: Option<String>,
+    pub error: Option<SolrErrorBody>,
+    pub requestid: Option<String>,
+}
+
+impl SolrCollectionsAdminType {
+    /// Check whether solr carried out the action without any failures
+    pub fn is_success(&self) -> bool {
+        self.responseHeader.status == 0 && self.failure.is_none() && self.error.is_none()
+    }
+
+    /// Names of any cores created by the action (eg. by CREATE or ADDREPLICA)
+    pub fn cores(&self) -> Vec<String> {
+        match &self.success {
+            Some(Value::Object(nodes)) => nodes
+                .values()
+                .filter_map(|node| node.get("core").and_then(Value::as_str))
+                .map(String::from)
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl fmt::Display for SolrCollectionsAdminType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+
+/// Standard structure for most calls to /admin/cores (eg. CREATE, UNLOAD or SWAP)
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrCoreAdminType {
+    pub responseHeader: SolrResponseHeader,
+    pub core: Option<String>,
+    pub error: Option<SolrErrorBody>,
+}
+
+impl SolrCoreAdminType {
+    /// Check whether solr carried out the action
+    pub fn is_success(&self) -> bool {
+        self.responseHeader.status == 0 && self.error.is_none()
+    }
+}
+
+impl fmt::Display for SolrCoreAdminType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+
+/// Struct to match the index statistics of a core, from /admin/cores?action=STATUS or /admin/luke
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrCoreIndexStats {
+    pub numDocs: Option<u64>,
+    pub maxDoc: Option<u64>,
+    pub deletedDocs: Option<u64>,
+    pub version: Option<u64>,
+    pub segmentCount: Option<u32>,
+    pub current: Option<bool>,
+    pub hasDeletions: Option<bool>,
+    pub directory: Option<String>,
+    pub segmentsFile: Option<String>,
+    pub segmentsFileSizeInBytes: Option<u64>,
+    #[serde(default)]
+    pub userData: BTreeMap<String, String>,
+    pub lastModified: Option<String>,
+    pub sizeInBytes: Option<u64>,
+    /// Human readable index size, eg. "1.2 MB"
+    pub size: Option<String>,
+}
+
+/// Struct to match the status of a single core, from /admin/cores?action=STATUS
+///
+/// Solr returns an empty status for a core name it does not know, so all fields are optional.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrCoreInfo {
+    pub name: Option<String>,
+    pub instanceDir: Option<String>,
+    pub dataDir: Option<String>,
+    pub config: Option<String>,
+    pub schema: Option<String>,
+    pub startTime: Option<String>,
+    /// Milliseconds since the core was loaded
+    pub uptime: Option<u64>,
+    pub index: Option<SolrCoreIndexStats>,
+}
+
+/// Standard structure for a call to /admin/cores?action=STATUS
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrCoreStatusType {
+    pub responseHeader: SolrResponseHeader,
+    /// Cores which failed to load, mapped to the reason
+    #[serde(default)]
+    pub initFailures: BTreeMap<String, String>,
+    #[serde(default)]
+    pub status: BTreeMap<String, SolrCoreInfo>,
+}
+
+impl SolrCoreStatusType {
+    /// Look up the status of a loaded core
+    pub fn core(&self, name: &str) -> Option<&SolrCoreInfo> {
+        self.status.get(name).filter(|core| core.name.is_some())
+    }
+}
+
+impl fmt::Display for SolrCoreStatusType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+
+/// Standard structure for a call to /admin/collections?action=LISTALIASES
+///
+/// Aliases are mapped to the comma separated list of collections they point to.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrListAliasesType {
+    pub responseHeader: SolrResponseHeader,
+    #[serde(default)]
+    pub aliases: BTreeMap<String, String>,
+    #[serde(default)]
+    pub properties: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl SolrListAliasesType {
+    /// List the collections an alias points to
+    pub fn collections(&self, alias: &str) -> Vec<&str> {
+        self.aliases
+            .get(alias)
+            .map(|collections| collections.split(',').collect())
+            .unwrap_or_default()
+    }
+}
+
+impl fmt::Display for SolrListAliasesType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+
+/// Struct to match the details of a single backup point
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrBackupPoint {
+    pub collection: Option<String>,
+    pub backupId: Option<u32>,
+    pub numShards: Option<u32>,
+    pub indexVersion: Option<String>,
+    pub startTime: Option<String>,
+    pub endTime: Option<String>,
+    pub indexFileCount: Option<u64>,
+    pub uploadedIndexFileCount: Option<u64>,
+    #[serde(rename = "collection.configName")]
+    pub configName: Option<String>,
+}
+
+impl fmt::Display for SolrBackupPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+
+/// Standard structure for a call to /admin/collections?action=BACKUP
+///
+/// Incremental backups report the backup point they created under response.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrBackupType {
+    pub responseHeader: SolrResponseHeader,
+    pub response: Option<SolrBackupPoint>,
+    pub success: Option<Value>,
+    pub failure: Option<Value>,
+    pub error: Option<SolrErrorBody>,
+}
+
+impl fmt::Display for SolrBackupType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+
+/// Standard structure for a call to /admin/collections?action=LISTBACKUP
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrListBackupType {
+    pub responseHeader: SolrResponseHeader,
+    pub collection: Option<String>,
+    #[serde(default)]
+    pub backups: Vec<SolrBackupPoint>,
+}
+
+impl fmt::Display for SolrListBackupType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+
+/// Struct to match a single replica's part of a collection snapshot
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrReplicaSnapshot {
+    pub core: String,
+    pub shard_id: Option<String>,
+    pub indexDirPath: Option<String>,
+    pub generationNumber: Option<u64>,
+    pub leader: Option<bool>,
+}
+
+/// Struct to match a collection snapshot
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrSnapshot {
+    pub name: String,
+    pub status: Option<String>,
+    pub creationDate: Option<String>,
+    #[serde(default)]
+    pub replicaSnapshots: Vec<SolrReplicaSnapshot>,
+}
+
+impl fmt::Display for SolrSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+
+/// Standard structure for a call to /admin/collections?action=LISTSNAPSHOTS
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrListSnapshotsType {
+    pub responseHeader: SolrResponseHeader,
+    #[serde(default)]
+    pub snapshots: BTreeMap<String, SolrSnapshot>,
+}
+
+impl fmt::Display for SolrListSnapshotsType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+
+/// States reported by REQUESTSTATUS for an async collections admin request
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum SolrAsyncState {
+    Submitted,
+    Running,
+    Completed,
+    Failed,
+    #[serde(rename = "notfound")]
+    NotFound,
+}
+
+/// Struct to match the status block of a REQUESTSTATUS response
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrAsyncStatus {
+    pub state: SolrAsyncState,
+    pub msg: Option<String>,
+}
+
+/// Standard structure for a call to /admin/collections?action=REQUESTSTATUS
+///
+/// Once the request has completed (or failed), the response also holds the outcome of the
+/// original action, in the same shape as the synchronous response.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrRequestStatusType {
+    pub responseHeader: SolrResponseHeader,
+    pub status: SolrAsyncStatus,
+    pub success: Option<Value>,
+    pub failure: Option<Value>,
+    pub error: Option<SolrErrorBody>,
+}
+
+impl fmt::Display for SolrRequestStatusType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+
+/// Standard structure for a call to /admin/configs?action=LIST
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrListConfigSetsType {
+    pub responseHeader: SolrResponseHeader,
+    pub configSets: Vec<String>,
+}
+
+impl fmt::Display for SolrListConfigSetsType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+
+/// Standard structure for a call to /schema
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrSchemaType {
+    pub responseHeader: SolrResponseHeader,
+    pub schema: SolrSchema,
+}
+
+impl fmt::Display for SolrSchemaType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+
+/// Standard structure for a call to /config, holding the effective (merged) configuration
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrConfigType {
+    pub responseHeader: SolrResponseHeader,
+    pub config: Value,
+}
+
+impl fmt::Display for SolrConfigType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+
+/// Struct to match the Config API overlay (configoverlay.json)
+///
+/// Components added through the Config API are held under their type, eg. requestHandler or
+/// searchComponent, keyed by name.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+pub struct SolrConfigOverlay {
+    pub znodeVersion: Option<i64>,
+    #[serde(default)]
+    pub props: BTreeMap<String, Value>,
+    #[serde(default)]
+    pub userProps: BTreeMap<String, Value>,
+    #[serde(flatten)]
+    pub components: BTreeMap<String, BTreeMap<String, Value>>,
+}
+
+impl SolrConfigOverlay {
+    /// Look up a component added through the Config API, eg. ("requestHandler", "/films")
+    pub fn component(&self, component_type: &str, name: &str) -> Option<&Value> {
+        self.components.get(component_type)?.get(name)
+    }
+}
+
+/// Standard structure for a call to /config/overlay
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrConfigOverlayType {
+    pub responseHeader: SolrResponseHeader,
+    pub overlay: SolrConfigOverlay,
+}
+
+impl fmt::Display for SolrConfigOverlayType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+
+/// Struct to match the version solr stores with each param set
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrParamSetVersion {
+    pub v: i64,
+}
+
+/// Struct to match a single param set from the Request Parameters API
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrParamSet {
+    #[serde(rename = "")]
+    pub version: Option<SolrParamSetVersion>,
+    #[serde(flatten)]
+    pub params: BTreeMap<String, Value>,
+}
+
+/// Struct to match the body of a /config/params response
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrParamSets {
+    pub znodeVersion: Option<i64>,
+    #[serde(default)]
+    pub params: BTreeMap<String, SolrParamSet>,
+}
+
+/// Standard structure for a call to /config/params (or /config/params/<name>)
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrParamSetsType {
+    pub responseHeader: SolrResponseHeader,
+    pub response: SolrParamSets,
+}
+
+impl fmt::Display for SolrParamSetsType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+
+/// Standard structure for a call to /admin/ping
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrPingType {
+    pub responseHeader: SolrResponseHeader,
+    pub status: Option<String>,
+    pub error: Option<SolrErrorBody>,
+}
+
+impl SolrPingType {
+    /// Check whether the ping query succeeded
+    pub fn is_ok(&self) -> bool {
+        self.status.as_deref() == Some("OK") && self.error.is_none()
+    }
+}
+
+impl fmt::Display for SolrPingType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+
+/// Standard structure for a call to /admin/info/health
+///
+/// Unhealthy nodes report a status of FAILURE, with the reason in the message.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrHealthType {
+    pub responseHeader: SolrResponseHeader,
+    pub status: Option<String>,
+    pub message: Option<String>,
+    pub error: Option<SolrErrorBody>,
+}
+
+impl SolrHealthType {
+    /// Check whether the node reported itself healthy
+    pub fn is_healthy(&self) -> bool {
+        self.status.as_deref() == Some("OK") && self.error.is_none()
+    }
+}
+
+impl fmt::Display for SolrHealthType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+
+/// Struct to match the solr and lucene versions, from /admin/info/system
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrLuceneInfo {
+    #[serde(rename = "solr-spec-version")]
+    pub solrSpecVersion: String,
+    #[serde(rename = "solr-impl-version")]
+    pub solrImplVersion: Option<String>,
+    #[serde(rename = "lucene-spec-version")]
+    pub luceneSpecVersion: String,
+    #[serde(rename = "lucene-impl-version")]
+    pub luceneImplVersion: Option<String>,
+}
+
+/// Struct to match the JVM memory usage in bytes, from /admin/info/system
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SolrJvmMemoryRaw {
+    pub free: u64,
+    pub total: u64,
+    pub max: u64,
+    pub used: u64,
+    #[serde(rename = "used%")]
+    pub usedPercent: Option<f64>,
+}
+
+/// Struct to match the JVM memory usage, from /admin/info/system
+///
+/// The top level values are human readable (eg. "1.2 GB"), with the byte counts under raw.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SolrJvmMemory {
+    pub free: String,
+    pub total: String,
+    pub max: String,
+    pub used: String,
+    pub raw: SolrJvmMemoryRaw,
+}
+
+/// Struct to match the JVM details, from /admin/info/system
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SolrJvmInfo {
+    pub version: String,
+    pub name: String,
+    pub processors: u32,
+    pub memory: SolrJvmMemory,
+}
+
+/// Standard structure for a call to /admin/info/system
+///
+/// The operating system details in system vary between platforms, so are left untyped.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SolrSystemInfoType {
+    pub responseHeader: SolrResponseHeader,
+    /// Either std or solrcloud
+    pub mode: Option<String>,
+    pub zkHost: Option<String>,
+    pub solr_home: Option<String>,
+    pub core_root: Option<String>,
+    pub node: Option<String>,
+    pub lucene: SolrLuceneInfo,
+    pub jvm: SolrJvmInfo,
+    pub system: Option<Value>,
+}
+
+impl fmt::Display for SolrSystemInfoType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+
+/// Standard structure for a call to /replication?command=indexversion
+///
+/// Both values are 0 until the core has a replicable commit.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrIndexVersionType {
+    pub responseHeader: SolrResponseHeader,
+    pub indexversion: u64,
+    pub generation: u64,
+}
+
+impl fmt::Display for SolrIndexVersionType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+
+/// Standard structure for replication commands which only report a status (eg. fetchindex,
+/// disablepoll or backup)
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrReplicationStatusType {
+    pub responseHeader: SolrResponseHeader,
+    pub status: Option<String>,
+    pub message: Option<String>,
+    pub exception: Option<String>,
+    pub error: Option<SolrErrorBody>,
+}
+
+impl SolrReplicationStatusType {
+    /// Check whether the replication handler accepted the command
+    pub fn is_ok(&self) -> bool {
+        self.status.as_deref() == Some("OK") && self.error.is_none()
+    }
+}
+
+impl fmt::Display for SolrReplicationStatusType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+
+/// Struct to match the leader section of /replication?command=details
+///
+/// Solr versions before 8.7 call this section master.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrReplicationLeader {
+    #[serde(default)]
+    pub confFiles: Vec<String>,
+    #[serde(default)]
+    pub replicateAfter: Vec<String>,
+    pub replicationEnabled: Option<String>,
+    pub replicableVersion: Option<u64>,
+    pub replicableGeneration: Option<u64>,
+}
+
+impl SolrReplicationLeader {
+    pub fn is_replication_enabled(&self) -> bool {
+        self.replicationEnabled.as_deref() == Some("true")
+    }
+}
+
+/// Struct to match the follower section of /replication?command=details
+///
+/// Solr versions before 8.7 call this section slave, and the leader URL masterUrl. While a fetch is
+/// running, its progress (eg. bytesDownloaded, totalPercent) is kept in other.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrReplicationFollower {
+    #[serde(alias = "masterUrl")]
+    pub leaderUrl: Option<String>,
+    pub pollInterval: Option<String>,
+    pub isPollingDisabled: Option<String>,
+    pub isReplicating: Option<String>,
+    pub nextExecutionAt: Option<String>,
+    pub indexReplicatedAt: Option<String>,
+    pub replicationFailedAt: Option<String>,
+    pub timesIndexReplicated: Option<u64>,
+    pub timesFailed: Option<u64>,
+    pub lastCycleBytesDownloaded: Option<u64>,
+    #[serde(flatten)]
+    pub other: BTreeMap<String, Value>,
+}
+
+impl SolrReplicationFollower {
+    pub fn is_polling_disabled(&self) -> bool {
+        self.isPollingDisabled.as_deref() == Some("true")
+    }
+
+    pub fn is_replicating(&self) -> bool {
+        self.isReplicating.as_deref() == Some("true")
+    }
+}
+
+/// Struct to match the details of a core's replication, from /replication?command=details
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrReplicationDetails {
+    /// Human readable index size, eg. "1.2 MB"
+    pub indexSize: Option<String>,
+    pub indexPath: Option<String>,
+    pub indexVersion: Option<u64>,
+    pub generation: Option<u64>,
+    #[serde(alias = "isMaster")]
+    pub isLeader: Option<String>,
+    #[serde(alias = "isSlave")]
+    pub isFollower: Option<String>,
+    #[serde(alias = "master")]
+    pub leader: Option<SolrReplicationLeader>,
+    #[serde(alias = "slave")]
+    pub follower: Option<SolrReplicationFollower>,
+    /// Commit points held for replication, as solr NamedLists
+    pub commits: Option<Value>,
+    /// Status of the most recent backup, as a solr NamedList
+    pub backup: Option<Value>,
+}
+
+impl SolrReplicationDetails {
+    pub fn is_leader(&self) -> bool {
+        self.isLeader.as_deref() == Some("true")
+    }
+
+    pub fn is_follower(&self) -> bool {
+        self.isFollower.as_deref() == Some("true")
+    }
+}
+
+/// Standard structure for a call to /replication?command=details
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrReplicationDetailsType {
+    pub responseHeader: SolrResponseHeader,
+    pub details: SolrReplicationDetails,
+}
+
+impl fmt::Display for SolrReplicationDetailsType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+
+/// Struct to match the status of a restore, from /replication?command=restorestatus
+///
+/// Status is one of "In Progress", "success" or "failed", or "No restore actions in progress" if
+/// no restore has been run since the core was loaded.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrRestoreProgress {
+    pub status: String,
+    pub snapshotName: Option<String>,
+    pub exception: Option<String>,
+}
+
+impl SolrRestoreProgress {
+    pub fn is_in_progress(&self) -> bool {
+        self.status == "In Progress"
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.status == "success"
+    }
+}
+
+/// Standard structure for a call to /replication?command=restorestatus
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrRestoreStatusType {
+    pub responseHeader: SolrResponseHeader,
+    pub restorestatus: SolrRestoreProgress,
+}
+
+impl fmt::Display for SolrRestoreStatusType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+
+/// Standard structure for a call to /admin/authentication
+///
+/// The authentication section is missing when security.json doesn't enable authentication.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrAuthenticationType {
+    pub responseHeader: SolrResponseHeader,
+    #[serde(rename = "authentication.enabled")]
+    pub authenticationEnabled: Option<bool>,
+    pub authentication: Option<SolrAuthenticationConfig>,
+}
+
+impl fmt::Display for SolrAuthenticationType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+
+/// Standard structure for a call to /admin/authorization
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrAuthorizationType {
+    pub responseHeader: SolrResponseHeader,
+    #[serde(rename = "authorization.enabled")]
+    pub authorizationEnabled: Option<bool>,
+    pub authorization: Option<SolrAuthorizationConfig>,
+}
+
+impl fmt::Display for SolrAuthorizationType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+
+/// Standard structure for a call to /update/extract with extractOnly=true
+///
+/// Solr returns the extracted content and metadata under keys named after the uploaded file.
+/// These are gathered here into the content and metadata fields, with the file's name kept
+/// alongside them.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(try_from = "RawExtractOnlyType")]
+pub struct SolrExtractOnlyType {
+    pub responseHeader: SolrResponseHeader,
+    pub resource_name: String,
+    pub content: String,
+    pub metadata: BTreeMap<String, Vec<String>>,
+}
+
+impl fmt::Display for SolrExtractOnlyType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+
+#[derive(Deserialize)]
+struct RawExtractOnlyType {
+    responseHeader: SolrResponseHeader,
+    #[serde(flatten)]
+    extracted: BTreeMap<String, Value>,
+}
+
+impl TryFrom<RawExtractOnlyType> for SolrExtractOnlyType {
+    type Error = String;
+
+    fn try_from(raw: RawExtractOnlyType) -> Result<Self, Self::Error> {
+        let metadata_key = raw
+            .extracted
+            .keys()
+            .find(|key| key.ends_with("_metadata"))
+            .cloned()
+            .ok_or("No extracted metadata found in response")?;
+        let resource_name = metadata_key.trim_end_matches("_metadata").to_string();
+
+        let content = match raw.extracted.get(&resource_name) {
+            Some(Value::String(content)) => content.clone(),
+            _ => return Err(format!("No extracted content found for {}", resource_name)),
+        };
+
+        Ok(SolrExtractOnlyType {
+            responseHeader: raw.responseHeader,
+            metadata: named_list_of_strings(&raw.extracted[&metadata_key]),
+            resource_name,
+            content,
+        })
+    }
+}
+
+/// Convert a solr NamedList (either flat, as [key, value, key, value...], or a map) into a map of
+/// string lists
+fn named_list_of_strings(named_list: &Value) -> BTreeMap<String, Vec<String>> {
+    let pairs: Vec<(String, &Value)> = match named_list {
+        Value::Array(flat) => flat
+            .chunks(2)
+            .filter_map(|pair| match pair {
+                [Value::String(key), value] => Some((key.clone(), value)),
+                _ => None,
+            })
+            .collect(),
+        Value::Object(map) => map.iter().map(|(k, v)| (k.clone(), v)).collect(),
+        _ => Vec::new(),
+    };
+
+    pairs
+        .into_iter()
+        .map(|(key, value)| {
+            let values = match value {
+                Value::Array(values) => values.iter().map(value_to_string).collect(),
+                other => vec![value_to_string(other)],
+            };
+            (key, values)
+        })
+        .collect()
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+//
+// Cluster state structs, shared between zookeeper state and admin responses
+//
+/// Replica types in solrcloud
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum SolrReplicaType {
+    NRT,
+    TLOG,
+    PULL,
+}
+
+/// Struct to match a replica entry in a collection's state
+///
+/// Solr records the leader flag as the string "true" (and leaves it out otherwise), so please use
+/// is_leader() rather than reading this directly.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrReplicaState {
+    pub core: String,
+    pub base_url: Option<String>,
+    pub node_name: String,
+    pub state: String,
+    #[serde(rename = "type")]
+    pub replica_type: Option<SolrReplicaType>,
+    pub leader: Option<String>,
+}
+
+impl SolrReplicaState {
+    /// Check whether this replica is the leader of its shard
+    pub fn is_leader(&self) -> bool {
+        self.leader.as_deref() == Some("true")
+    }
+
+    /// Check whether this replica is active (ie. not down or recovering)
+    pub fn is_active(&self) -> bool {
+        self.state == "active"
+    }
+
+    /// URL of this replica's core, eg. http://10.0.0.1:8983/solr/films_shard1_replica_n1
+    ///
+    /// Newer versions of solr leave out base_url, in which case it is built from the node name
+    /// and url_scheme, which should be the cluster's urlScheme property (http unless set).
+    pub fn core_url(&self, url_scheme: &str) -> String {
+        let base_url = match &self.base_url {
+            Some(base_url) => base_url.clone(),
+            None => format!("{}://{}", url_scheme, self.node_name.replace("_", "/")),
+        };
+        format!("{}/{}", base_url, self.core)
+    }
+}
+
+impl fmt::Display for SolrReplicaState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+
+/// Struct to match a shard entry in a collection's state
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrShardState {
+    pub range: Option<String>,
+    pub state: String,
+    #[serde(default)]
+    pub replicas: BTreeMap<String, SolrReplicaState>,
+    pub parent: Option<String>,
+    pub health: Option<String>,
+}
+
+impl SolrShardState {
+    /// Parse the shard's hash range (eg. "80000000-ffffffff") into inclusive signed bounds
+    pub fn hash_range(&self) -> Option<(i32, i32)> {
+        let range = self.range.as_ref()?;
+        let mut bounds = range.splitn(2, '-');
+        let min = u32::from_str_radix(bounds.next()?, 16).ok()?;
+        let max = u32::from_str_radix(bounds.next()?, 16).ok()?;
+        Some((min as i32, max as i32))
+    }
+
+    /// Check whether this shard is active (ie. not being built or retired by a shard split)
+    pub fn is_active(&self) -> bool {
+        self.state == "active"
+    }
+
+    /// Find the current leader replica of this shard
+    pub fn leader(&self) -> Option<&SolrReplicaState> {
+        self.replicas.values().find(|replica| replica.is_leader())
+    }
+}
+
+impl fmt::Display for SolrShardState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+
+/// Struct to match the document router of a collection
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrRouter {
+    pub name: String,
+    pub field: Option<String>,
+}
+
+/// Struct to match a collection's state, as stored in zookeeper under
+/// /collections/<collection>/state.json
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrCollectionState {
+    pub shards: BTreeMap<String, SolrShardState>,
+    pub router: SolrRouter,
+    pub znodeVersion: Option<u32>,
+    pub configName: Option<String>,
+    pub health: Option<String>,
+    pub aliases: Option<Vec<String>>,
+}
+
+impl SolrCollectionState {
+    /// Find the current leader of each shard, keyed by shard name
+    pub fn leaders(&self) -> BTreeMap<&str, &SolrReplicaState> {
+        self.shards
+            .iter()
+            .filter_map(|(name, shard)| shard.leader().map(|leader| (name.as_str(), leader)))
+            .collect()
+    }
+}
+
+impl fmt::Display for SolrCollectionState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+
+/// Struct to match the whole cluster, as returned by CLUSTERSTATUS
+///
+/// This can also be read straight from zookeeper with `ZkSolrClient::cluster_state`.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrClusterState {
+    #[serde(default)]
+    pub collections: BTreeMap<String, SolrCollectionState>,
+    /// Aliases, mapped to the comma separated list of collections they point to
+    #[serde(default)]
+    pub aliases: BTreeMap<String, String>,
+    /// Node roles (eg. overseer), mapped to the nodes holding them
+    #[serde(default)]
+    pub roles: BTreeMap<String, Vec<String>>,
+    #[serde(default)]
+    pub live_nodes: Vec<String>,
+    pub properties: Option<BTreeMap<String, Value>>,
+}
+
+impl SolrClusterState {
+    /// Look up a collection by name, or by an alias pointing at a single collection
+    pub fn collection(&self, name: &str) -> Option<&SolrCollectionState> {
+        self.collections.get(name).or_else(|| {
+            self.aliases
+                .get(name)
+                .filter(|target| !target.contains(','))
+                .and_then(|target| self.collections.get(target))
+        })
+    }
+
+    /// List the replicas which are not serving requests, as (collection, shard, replica name)
+    ///
+    /// This covers replicas which are not active, and those whose node is no longer live (solr
+    /// does not always update the state of replicas on a node which has died).
+    pub fn unhealthy_replicas(&self) -> Vec<(&str, &str, &str)> {
+        let mut unhealthy = Vec::new();
+        for (collection_name, collection) in &self.collections {
+            for (shard_name, shard) in collection.shards.iter().filter(|(_, s)| s.is_active()) {
+                for (replica_name, replica) in &shard.replicas {
+                    if !replica.is_active() || !self.live_nodes.contains(&replica.node_name) {
+                        unhealthy.push((
+                            collection_name.as_str(),
+                            shard_name.as_str(),
+                            replica_name.as_str(),
+                        ));
+                    }
+                }
+            }
+        }
+        unhealthy
+    }
+}
+
+impl fmt::Display for SolrClusterState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+
+/// Standard structure for a call to /admin/collections?action=CLUSTERSTATUS
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrClusterStatusType {
+    pub responseHeader: SolrResponseHeader,
+    pub cluster: SolrClusterState,
+}
+
+impl fmt::Display for SolrClusterStatusType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+
+//
+// Metrics structs, from /admin/metrics
+//
+/// Struct to match a timer metric, eg. QUERY./select.requestTimes
+///
+/// Rates are events per second, and times are in milliseconds.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SolrTimer {
+    pub count: u64,
+    pub meanRate: f64,
+    #[serde(rename = "1minRate")]
+    pub oneMinRate: f64,
+    #[serde(rename = "5minRate")]
+    pub fiveMinRate: f64,
+    #[serde(rename = "15minRate")]
+    pub fifteenMinRate: f64,
+    pub min_ms: f64,
+    pub max_ms: f64,
+    pub mean_ms: f64,
+    pub median_ms: f64,
+    pub stddev_ms: f64,
+    pub p75_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+    pub p999_ms: f64,
+    pub sum_ms: Option<f64>,
+}
+
+/// Struct to match a histogram metric
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SolrHistogram {
+    pub count: u64,
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub median: f64,
+    pub stddev: f64,
+    pub p75: f64,
+    pub p95: f64,
+    pub p99: f64,
+    pub p999: f64,
+}
+
+/// Struct to match a meter metric, with rates in events per second
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SolrMeter {
+    pub count: u64,
+    pub meanRate: f64,
+    #[serde(rename = "1minRate")]
+    pub oneMinRate: f64,
+    #[serde(rename = "5minRate")]
+    pub fiveMinRate: f64,
+    #[serde(rename = "15minRate")]
+    pub fifteenMinRate: f64,
+}
+
+/// A single metric value
+///
+/// With compact output (solr's default), counters and simple gauges are returned as bare values,
+/// so can't be told apart, and are both held as Value. Gauges can also be maps of values, eg.
+/// cache statistics.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum SolrMetric {
+    Timer(SolrTimer),
+    Histogram(SolrHistogram),
+    Meter(SolrMeter),
+    /// A counter, from non-compact output
+    Counter {
+        count: u64,
+    },
+    /// A gauge, from non-compact output
+    Gauge {
+        value: Value,
+    },
+    Value(Value),
+}
+
+impl SolrMetric {
+    /// The metric's headline number: its count, or its value if that is numeric (or a bool)
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            SolrMetric::Timer(timer) => Some(timer.count as f64),
+            SolrMetric::Histogram(histogram) => Some(histogram.count as f64),
+            SolrMetric::Meter(meter) => Some(meter.count as f64),
+            SolrMetric::Counter { count } => Some(*count as f64),
+            SolrMetric::Gauge { value } | SolrMetric::Value(value) => match value {
+                Value::Number(number) => number.as_f64(),
+                Value::Bool(flag) => Some(if *flag { 1.0 } else { 0.0 }),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// Standard structure for a call to /admin/metrics
+///
+/// Metrics are grouped by registry (eg. solr.jvm, solr.node or solr.core.films), then keyed by
+/// metric name. Responses to requests using the key parameter, which solr keys as
+/// registry:metric, are grouped the same way.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(try_from = "RawMetricsType")]
+pub struct SolrMetricsType {
+    pub responseHeader: SolrResponseHeader,
+    pub metrics: BTreeMap<String, BTreeMap<String, SolrMetric>>,
+}
+
+impl SolrMetricsType {
+    /// Look up a single metric, eg. ("solr.jvm", "memory.heap.used")
+    pub fn metric(&self, registry: &str, name: &str) -> Option<&SolrMetric> {
+        self.metrics.get(registry)?.get(name)
+    }
+}
+
+impl fmt::Display for SolrMetricsType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+
+#[derive(Deserialize)]
+struct RawMetricsType {
+    responseHeader: SolrResponseHeader,
+    #[serde(default)]
+    metrics: BTreeMap<String, Value>,
+}
+
+impl TryFrom<RawMetricsType> for SolrMetricsType {
+    type Error = String;
+
+    fn try_from(raw: RawMetricsType) -> Result<Self, Self::Error> {
+        let mut metrics: BTreeMap<String, BTreeMap<String, SolrMetric>> = BTreeMap::new();
+        for (key, value) in raw.metrics {
+            // registry names never hold a colon, so this is a registry:metric key
+            if let Some(split) = key.find(':') {
+                let metric = serde_json::from_value(value).map_err(|e| e.to_string())?;
+                metrics
+                    .entry(key[..split].to_string())
+                    .or_default()
+                    .insert(key[split + 1..].to_string(), metric);
+            } else {
+                let registry = serde_json::from_value(value).map_err(|e| e.to_string())?;
+                metrics.insert(key, registry);
+            }
+        }
+
+        Ok(SolrMetricsType {
+            responseHeader: raw.responseHeader,
+            metrics,
+        })
+    }
+}
+
+//
+// Managed resources structs, from /schema/analysis
+//
+/// Struct to match the initArgs of a managed resource
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrManagedInitArgs {
+    pub ignoreCase: Option<bool>,
+    pub format: Option<String>,
+}
+
+/// Struct to match a managed synonym map, from /schema/analysis/synonyms/<name>
+///
+/// Equivalent synonyms are held as one mapping per term, each to the whole set.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrManagedSynonyms {
+    pub initArgs: SolrManagedInitArgs,
+    pub initializedOn: Option<String>,
+    pub updatedSinceInit: Option<String>,
+    #[serde(default)]
+    pub managedMap: BTreeMap<String, Vec<String>>,
+}
+
+/// Standard structure for a call to /schema/analysis/synonyms/<name>
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrManagedSynonymsType {
+    pub responseHeader: SolrResponseHeader,
+    pub synonymMappings: SolrManagedSynonyms,
+}
+
+impl fmt::Display for SolrManagedSynonymsType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+
+/// Struct to match a managed stopword list, from /schema/analysis/stopwords/<name>
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrManagedStopwords {
+    pub initArgs: SolrManagedInitArgs,
+    pub initializedOn: Option<String>,
+    pub updatedSinceInit: Option<String>,
+    #[serde(default)]
+    pub managedList: Vec<String>,
+}
+
+/// Standard structure for a call to /schema/analysis/stopwords/<name>
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrManagedStopwordsType {
+    pub responseHeader: SolrResponseHeader,
+    pub wordSet: SolrManagedStopwords,
+}
+
+impl fmt::Display for SolrManagedStopwordsType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+
+//
+// Luke structs, from /admin/luke
+//
+/// Struct to match a field's details as found in the index, from /admin/luke
+///
+/// Flags are strings of single letter codes (eg. "ITS-----OF------"), explained by the info
+/// section of the response. Index flags are read from a stored value, so are missing (or a note
+/// such as "(unstored field)") for fields which aren't stored.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrLukeField {
+    #[serde(rename = "type")]
+    pub field_type: Option<String>,
+    pub schema: Option<String>,
+    pub dynamicBase: Option<String>,
+    pub index: Option<String>,
+    /// Number of documents holding the field
+    pub docs: Option<u64>,
+    /// Number of distinct terms in the field
+    pub distinct: Option<u64>,
+    /// Most frequent terms, with their document counts
+    #[serde(default, deserialize_with = "deserialize_term_counts")]
+    pub topTerms: Vec<(String, u64)>,
+    /// Number of terms by document count, bucketed by powers of two
+    #[serde(default, deserialize_with = "deserialize_term_counts")]
+    pub histogram: Vec<(String, u64)>,
+}
+
+impl SolrLukeField {
+    /// List the flags which differ between the schema and what was actually indexed
+    ///
+    /// Luke reads the index flags from a document rebuilt from stored values, where lucene only
+    /// restores term vectors (V), omitNorms (O) and the index options (F and P) of string values.
+    /// Numeric and date values come back as plain stored fields, which aren't marked as indexed,
+    /// so O, F and P are only compared when the index flags include I. This is empty if the index
+    /// flags aren't available, so a non-empty result usually means documents were indexed under
+    /// an older schema.
+    pub fn schema_mismatches(&self) -> Vec<char> {
+        let (schema, index) = match (&self.schema, &self.index) {
+            (Some(schema), Some(index)) if !index.starts_with('(') => (schema, index),
+            _ => return Vec::new(),
+        };
+        let comparable = if index.contains('I') { "SVOFP" } else { "SV" };
+        comparable
+            .chars()
+            .filter(|flag| schema.contains(*flag) != index.contains(*flag))
+            .collect()
+    }
+}
+
+/// Struct to match a field or dynamic field declared in the schema, from /admin/luke?show=schema
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrLukeSchemaField {
+    #[serde(rename = "type")]
+    pub field_type: String,
+    pub flags: String,
+    pub required: Option<bool>,
+    pub default: Option<String>,
+    pub uniqueKey: Option<bool>,
+    pub positionIncrementGap: Option<u32>,
+    #[serde(default)]
+    pub copyDests: Vec<String>,
+    #[serde(default)]
+    pub copySources: Vec<String>,
+}
+
+/// Struct to match a field type declared in the schema, from /admin/luke?show=schema
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrLukeFieldType {
+    /// Fields using this type (may be missing when there are none)
+    pub fields: Option<Vec<String>>,
+    pub tokenized: bool,
+    pub className: String,
+    pub indexAnalyzer: Option<Value>,
+    pub queryAnalyzer: Option<Value>,
+    pub similarity: Option<Value>,
+}
+
+/// Struct to match the schema section of /admin/luke?show=schema
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrLukeSchema {
+    #[serde(default)]
+    pub fields: BTreeMap<String, SolrLukeSchemaField>,
+    #[serde(default)]
+    pub dynamicFields: BTreeMap<String, SolrLukeSchemaField>,
+    pub uniqueKeyField: Option<String>,
+    pub similarity: Option<Value>,
+    #[serde(default)]
+    pub types: BTreeMap<String, SolrLukeFieldType>,
+}
+
+/// Struct to match one indexed value of a document's field, from /admin/luke?show=doc
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrLukeDocField {
+    #[serde(rename = "type")]
+    pub field_type: Option<String>,
+    pub schema: Option<String>,
+    pub flags: Option<String>,
+    pub value: Option<String>,
+    pub internal: Option<String>,
+    pub docFreq: Option<u64>,
+}
+
+/// Struct to match the doc section of /admin/luke?show=doc
+///
+/// Solr lists a multi-valued field once per value, so the lucene view holds a list per field.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrLukeDoc {
+    pub docId: u64,
+    #[serde(default, deserialize_with = "deserialize_repeated_keys")]
+    pub lucene: BTreeMap<String, Vec<SolrLukeDocField>>,
+    /// The stored document, as returned by a select request
+    pub solr: Option<Value>,
+}
+
+/// Standard structure for a call to /admin/luke
+///
+/// Which sections are present depends on SolrLukeOptions::show: schema for Schema, doc for Doc,
+/// and index (with fields and info unless show is Index) otherwise.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrLukeType {
+    pub responseHeader: SolrResponseHeader,
+    pub index: Option<SolrCoreIndexStats>,
+    #[serde(default)]
+    pub fields: BTreeMap<String, SolrLukeField>,
+    pub schema: Option<SolrLukeSchema>,
+    pub doc: Option<SolrLukeDoc>,
+    /// Explanations of the flag codes, and other notes
+    pub info: Option<Value>,
+}
+
+impl SolrLukeType {
+    /// List the indexed fields whose flags differ from the schema, with the differing flags
+    pub fn schema_mismatches(&self) -> BTreeMap<&str, Vec<char>> {
+        self.fields
+            .iter()
+            .map(|(name, field)| (name.as_str(), field.schema_mismatches()))
+            .filter(|(_, mismatches)| !mismatches.is_empty())
+            .collect()
+    }
+}
+
+impl fmt::Display for SolrLukeType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+
+/// Read a solr NamedList of counts, either flat ([term, count, term, count...]) or as a map
+fn deserialize_term_counts<'de, D>(deserializer: D) -> Result<Vec<(String, u64)>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let named_list = Value::deserialize(deserializer)?;
+    let pairs: Vec<(String, &Value)> = match &named_list {
+        Value::Array(flat) => flat
+            .chunks(2)
+            .filter_map(|pair| match pair {
+                [Value::String(key), value] => Some((key.clone(), value)),
+                _ => None,
+            })
+            .collect(),
+        Value::Object(map) => map.iter().map(|(k, v)| (k.clone(), v)).collect(),
+        _ => Vec::new(),
+    };
+
+    pairs
+        .into_iter()
+        .map(|(key, value)| match value.as_u64() {
+            Some(count) => Ok((key, count)),
+            None => Err(serde::de::Error::custom(format!(
+                "expected a count for {}, found {}",
+                key, value
+            ))),
+        })
+        .collect()
+}
+
+/// Read a JSON object which may repeat keys, gathering the values for each key into a list
+fn deserialize_repeated_keys<'de, D, T>(
+    deserializer: D,
+) -> Result<BTreeMap<String, Vec<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    struct RepeatedKeys<T>(std::marker::PhantomData<T>);
+
+    impl<'de, T: Deserialize<'de>> serde::de::Visitor<'de> for RepeatedKeys<T> {
+        type Value = BTreeMap<String, Vec<T>>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "a map")
+        }
+
+        fn visit_map<A: serde::de::MapAccess<'de>>(
+            self,
+            mut map: A,
+        ) -> Result<Self::Value, A::Error> {
+            let mut values: BTreeMap<String, Vec<T>> = BTreeMap::new();
+            while let Some((key, value)) = map.next_entry::<String, T>()? {
+                values.entry(key).or_default().push(value);
+            }
+            Ok(values)
+        }
+    }
+
+    deserializer.deserialize_map(RepeatedKeys(std::marker::PhantomData))
+}
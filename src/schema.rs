@@ -0,0 +1,426 @@
+//! Schema API model and bulk schema changes
+//!
+//! The structs here match the definitions returned by `GET /schema` (deserialised with
+//! `response_types::SolrSchemaType`), and are also used to build changes sent back to solr with
+//! `SolrCoreMethods::update_schema`. All the changes in a `SolrSchemaChanges` are sent in a single
+//! POST, which solr applies atomically.
+//!
+//! ```no_run
+//! # use tokio::runtime::Runtime;
+//! use stellr::prelude::*;
+//! use stellr::response_types::SolrUpdateType;
+//! use stellr::schema::{SolrSchemaChanges, SolrSchemaField};
+//!
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! let changes = SolrSchemaChanges::new()
+//!     .add_field(SolrSchemaField {
+//!         multi_valued: Some(false),
+//!         stored: Some(true),
+//!         ..SolrSchemaField::new("name", "text_general")
+//!     })?
+//!     .add_field(SolrSchemaField {
+//!         stored: Some(true),
+//!         ..SolrSchemaField::new("initial_release_date", "pdate")
+//!     })?;
+//!
+//! let solr_client = stellr::DirectSolrClient::new("http://localhost:8983/solr")?;
+//! let solr_request = solr_client.update_schema("films", &changes)?;
+//!
+//! # let mut rt = Runtime::new().unwrap();
+//! let result_struct =
+//! # rt.block_on(async {
+//!     solr_request
+//!         .call::<SolrUpdateType>()
+//!         .await
+//! # })
+//!     ?;
+//!
+//! assert_eq!(result_struct.responseHeader.status, 0);
+//! # Ok(()) }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Number, Value};
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::errors::SolrResult;
+use crate::requests::update_commands::SolrCommandList;
+
+/// A field, or dynamic field, definition
+///
+/// Properties left as None are inherited from the field type.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SolrSchemaField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub field_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indexed: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stored: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doc_values: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multi_valued: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uninvertible: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub use_doc_values_as_stored: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub omit_norms: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub omit_term_freq_and_positions: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub omit_positions: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub term_vectors: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub term_positions: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub term_offsets: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_missing_first: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_missing_last: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub large: Option<bool>,
+    /// Any other properties, eg. termPayloads
+    #[serde(flatten)]
+    pub properties: BTreeMap<String, Value>,
+}
+
+impl SolrSchemaField {
+    /// Define a field (or dynamic field pattern, eg. *_txt) of a given field type
+    pub fn new(name: &str, field_type: &str) -> Self {
+        SolrSchemaField {
+            name: String::from(name),
+            field_type: String::from(field_type),
+            ..Default::default()
+        }
+    }
+}
+
+/// A copy field rule, copying values from source (which may be a pattern) into dest
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct SolrCopyField {
+    pub source: String,
+    pub dest: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_chars: Option<u32>,
+}
+
+impl SolrCopyField {
+    pub fn new(source: &str, dest: &str) -> Self {
+        SolrCopyField {
+            source: String::from(source),
+            dest: String::from(dest),
+            max_chars: None,
+        }
+    }
+}
+
+/// A char filter, tokenizer or token filter within an analyzer
+///
+/// Components are named either by class (eg. solr.StandardTokenizerFactory) or, from solr 9, by
+/// SPI name (eg. standard). Any other arguments (eg. ignoreCase) are kept in args.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+pub struct SolrAnalysisComponent {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub class: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(flatten)]
+    pub args: BTreeMap<String, Value>,
+}
+
+impl SolrAnalysisComponent {
+    /// Name a component by its factory class
+    pub fn class(class: &str) -> Self {
+        SolrAnalysisComponent {
+            class: Some(String::from(class)),
+            ..Default::default()
+        }
+    }
+
+    /// Add an argument to the component
+    pub fn arg(mut self, name: &str, value: impl Into<Value>) -> Self {
+        self.args.insert(String::from(name), value.into());
+        self
+    }
+}
+
+/// An analysis chain for a text field type
+#[derive(Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SolrAnalyzer {
+    /// A complete analyzer class, used instead of a tokenizer and filters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub class: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub char_filters: Vec<SolrAnalysisComponent>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tokenizer: Option<SolrAnalysisComponent>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub filters: Vec<SolrAnalysisComponent>,
+}
+
+/// A field type definition
+///
+/// Field properties set on the type (eg. positionIncrementGap, or defaults such as docValues)
+/// are kept in properties.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SolrFieldType {
+    pub name: String,
+    pub class: String,
+    /// Analyzer used for both indexing and querying
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub analyzer: Option<SolrAnalyzer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index_analyzer: Option<SolrAnalyzer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query_analyzer: Option<SolrAnalyzer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multi_term_analyzer: Option<SolrAnalyzer>,
+    #[serde(flatten)]
+    pub properties: BTreeMap<String, Value>,
+}
+
+impl SolrFieldType {
+    pub fn new(name: &str, class: &str) -> Self {
+        SolrFieldType {
+            name: String::from(name),
+            class: String::from(class),
+            ..Default::default()
+        }
+    }
+}
+
+/// The similarity (scoring) configured for the schema
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrSimilarity {
+    pub class: String,
+    #[serde(flatten)]
+    pub args: BTreeMap<String, Value>,
+}
+
+/// A whole schema, as returned by GET /schema
+#[derive(Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SolrSchema {
+    pub name: Option<String>,
+    pub version: Option<Number>,
+    pub unique_key: Option<String>,
+    pub similarity: Option<SolrSimilarity>,
+    #[serde(default)]
+    pub field_types: Vec<SolrFieldType>,
+    #[serde(default)]
+    pub fields: Vec<SolrSchemaField>,
+    #[serde(default)]
+    pub dynamic_fields: Vec<SolrSchemaField>,
+    #[serde(default)]
+    pub copy_fields: Vec<SolrCopyField>,
+}
+
+impl SolrSchema {
+    /// Look up a field definition by name
+    pub fn field(&self, name: &str) -> Option<&SolrSchemaField> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    /// Look up a field type definition by name
+    pub fn field_type(&self, name: &str) -> Option<&SolrFieldType> {
+        self.field_types
+            .iter()
+            .find(|field_type| field_type.name == name)
+    }
+}
+
+impl fmt::Display for SolrSchema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", self)
+    }
+}
+
+/// Builder for a bulk Schema API request
+///
+/// Solr's schema endpoint accepts a single object with repeated command keys (eg. several
+/// add-field entries), which are applied in order, and all rolled back if any of them fail.
+#[derive(Serialize, Clone, Debug, Default, PartialEq)]
+#[serde(transparent)]
+pub struct SolrSchemaChanges {
+    commands: SolrCommandList,
+}
+
+impl SolrSchemaChanges {
+    pub fn new() -> SolrSchemaChanges {
+        SolrSchemaChanges::default()
+    }
+
+    pub fn add_field(self, field: SolrSchemaField) -> SolrResult<SolrSchemaChanges> {
+        self.push_serialized("add-field", &field)
+    }
+
+    /// Replace a field definition outright (properties not given revert to the type's defaults)
+    pub fn replace_field(self, field: SolrSchemaField) -> SolrResult<SolrSchemaChanges> {
+        self.push_serialized("replace-field", &field)
+    }
+
+    pub fn delete_field(self, name: &str) -> SolrSchemaChanges {
+        self.push("delete-field", json!({ "name": name }))
+    }
+
+    pub fn add_dynamic_field(self, field: SolrSchemaField) -> SolrResult<SolrSchemaChanges> {
+        self.push_serialized("add-dynamic-field", &field)
+    }
+
+    pub fn replace_dynamic_field(self, field: SolrSchemaField) -> SolrResult<SolrSchemaChanges> {
+        self.push_serialized("replace-dynamic-field", &field)
+    }
+
+    pub fn delete_dynamic_field(self, name: &str) -> SolrSchemaChanges {
+        self.push("delete-dynamic-field", json!({ "name": name }))
+    }
+
+    pub fn add_copy_field(self, copy_field: SolrCopyField) -> SolrResult<SolrSchemaChanges> {
+        self.push_serialized("add-copy-field", &copy_field)
+    }
+
+    pub fn delete_copy_field(self, source: &str, dest: &str) -> SolrSchemaChanges {
+        self.push(
+            "delete-copy-field",
+            json!({ "source": source, "dest": dest }),
+        )
+    }
+
+    pub fn add_field_type(self, field_type: SolrFieldType) -> SolrResult<SolrSchemaChanges> {
+        self.push_serialized("add-field-type", &field_type)
+    }
+
+    pub fn replace_field_type(self, field_type: SolrFieldType) -> SolrResult<SolrSchemaChanges> {
+        self.push_serialized("replace-field-type", &field_type)
+    }
+
+    pub fn delete_field_type(self, name: &str) -> SolrSchemaChanges {
+        self.push("delete-field-type", json!({ "name": name }))
+    }
+
+    /// Number of commands in this request
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    /// Check whether any commands have been added yet
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    fn push_serialized(
+        mut self,
+        command: &'static str,
+        definition: &impl Serialize,
+    ) -> SolrResult<SolrSchemaChanges> {
+        self.commands.push_serialized(command, definition)?;
+        Ok(self)
+    }
+
+    pub(crate) fn push(mut self, command: &'static str, value: Value) -> SolrSchemaChanges {
+        self.commands.push(command, value);
+        self
+    }
+}
+
+impl fmt::Display for SolrSchemaChanges {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.commands.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_schema_changes_body() {
+        let changes = SolrSchemaChanges::new()
+            .add_field(SolrSchemaField {
+                multi_valued: Some(false),
+                stored: Some(true),
+                ..SolrSchemaField::new("name", "text_general")
+            })
+            .unwrap()
+            .add_field(SolrSchemaField {
+                stored: Some(true),
+                ..SolrSchemaField::new("initial_release_date", "pdate")
+            })
+            .unwrap()
+            .add_copy_field(SolrCopyField::new("name", "_text_"))
+            .unwrap();
+
+        assert_eq!(
+            changes.to_string(),
+            r#"{"add-field":{"multiValued":false,"name":"name","stored":true,"type":"text_general"},"add-field":{"name":"initial_release_date","stored":true,"type":"pdate"},"add-copy-field":{"dest":"_text_","source":"name"}}"#
+        );
+    }
+
+    #[test]
+    fn test_field_type_round_trip() {
+        let mut field_type = SolrFieldType {
+            index_analyzer: Some(SolrAnalyzer {
+                tokenizer: Some(SolrAnalysisComponent::class(
+                    "solr.StandardTokenizerFactory",
+                )),
+                filters: vec![
+                    SolrAnalysisComponent::class("solr.StopFilterFactory")
+                        .arg("words", "stopwords.txt")
+                        .arg("ignoreCase", true),
+                    SolrAnalysisComponent::class("solr.LowerCaseFilterFactory"),
+                ],
+                ..Default::default()
+            }),
+            ..SolrFieldType::new("text_en_simple", "solr.TextField")
+        };
+        field_type
+            .properties
+            .insert(String::from("positionIncrementGap"), json!("100"));
+
+        let value = serde_json::to_value(&field_type).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "name": "text_en_simple",
+                "class": "solr.TextField",
+                "positionIncrementGap": "100",
+                "indexAnalyzer": {
+                    "tokenizer": {"class": "solr.StandardTokenizerFactory"},
+                    "filters": [
+                        {"class": "solr.StopFilterFactory", "words": "stopwords.txt", "ignoreCase": true},
+                        {"class": "solr.LowerCaseFilterFactory"}
+                    ]
+                }
+            })
+        );
+        assert_eq!(
+            serde_json::from_value::<SolrFieldType>(value).unwrap(),
+            field_type
+        );
+    }
+
+    #[test]
+    fn test_field_keeps_other_properties() {
+        let value =
+            json!({"name": "plot", "type": "text_en", "stored": true, "termPayloads": true});
+        let field: SolrSchemaField = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(field.stored, Some(true));
+        assert_eq!(field.properties.get("termPayloads"), Some(&json!(true)));
+        assert_eq!(serde_json::to_value(&field).unwrap(), value);
+    }
+}
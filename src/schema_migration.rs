@@ -0,0 +1,726 @@
+//! Declarative schema migrations, built on the Schema API
+//!
+//! A `SolrSchemaMigration` declares the field types, fields, dynamic fields and copy fields a
+//! collection should have. It can be built in rust, or read from a JSON (or, with the `toml`
+//! feature, TOML) file using the Schema API's own property names. Planning compares it against
+//! the live schema, and lists each add, replace and delete, flagging the changes which need
+//! existing documents to be reindexed. Applying a plan sends every change in one Schema API
+//! request, so solr either makes all of them or none.
+//!
+//! ```toml
+//! prune = true
+//!
+//! [[fields]]
+//! name = "genre"
+//! type = "string"
+//! multiValued = true
+//!
+//! [[copyFields]]
+//! source = "genre"
+//! dest = "_text_"
+//! ```
+//!
+//! ```no_run
+//! # use tokio::runtime::Runtime;
+//! use stellr::schema_migration::{apply_schema_migration, plan_schema_migration, SolrSchemaMigration};
+//!
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! let migration = SolrSchemaMigration::from_file("schema/films.json")?;
+//! let solr_client = stellr::DirectSolrClient::new("http://localhost:8983/solr")?;
+//!
+//! # let mut rt = Runtime::new().unwrap();
+//! # rt.block_on(async {
+//! // a dry run only reads the live schema
+//! let plan = plan_schema_migration(&solr_client, "films", &migration).await?;
+//! print!("{}", plan);
+//!
+//! if !plan.requires_reindex() {
+//!     apply_schema_migration(&solr_client, "films", &migration).await?;
+//! }
+//! # Ok::<(), stellr::SolrError>(())
+//! # })?;
+//! # Ok(()) }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use crate::clients::SolrCoreMethods;
+use crate::errors::{SolrError, SolrResult};
+use crate::requests::SolrRequest;
+use crate::response_types::{SolrSchemaType, SolrUpdateType};
+use crate::schema::{SolrCopyField, SolrFieldType, SolrSchema, SolrSchemaChanges, SolrSchemaField};
+
+/// The desired state of (part of) a schema
+///
+/// Declared definitions are compared as a whole: a property left as None is expected to be unset
+/// on the live definition too, as replacing a definition in solr resets any properties it omits.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SolrSchemaMigration {
+    pub field_types: Vec<SolrFieldType>,
+    pub fields: Vec<SolrSchemaField>,
+    pub dynamic_fields: Vec<SolrSchemaField>,
+    pub copy_fields: Vec<SolrCopyField>,
+    /// Delete live definitions which are not declared (default false)
+    ///
+    /// The uniqueKey field, solr's internal fields (eg. _version_ or _root_), fields named by a
+    /// declared copy field, and field types still in use are never deleted. Deleting a field also
+    /// deletes the live copy fields to or from it.
+    pub prune: bool,
+    /// Apply changes which need a reindex, rather than refusing them (default false)
+    pub allow_reindex: bool,
+}
+
+impl SolrSchemaMigration {
+    /// Parse a migration from JSON, eg. `{"fields": [{"name": "genre", "type": "string"}]}`
+    pub fn from_json(json: &str) -> SolrResult<Self> {
+        serde_json::from_str(json).map_err(|e| SolrError::SchemaError(e.to_string()))
+    }
+
+    /// Parse a migration from TOML, using the same property names as JSON
+    #[cfg(feature = "toml")]
+    pub fn from_toml(toml: &str) -> SolrResult<Self> {
+        toml::from_str(toml).map_err(|e| SolrError::SchemaError(e.to_string()))
+    }
+
+    /// Read a migration from a file, parsed as TOML if it has a .toml extension, else as JSON
+    pub fn from_file(path: impl AsRef<Path>) -> SolrResult<Self> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)?;
+        match path.extension().and_then(|extension| extension.to_str()) {
+            #[cfg(feature = "toml")]
+            Some("toml") => Self::from_toml(&contents),
+            #[cfg(not(feature = "toml"))]
+            Some("toml") => Err(SolrError::SchemaError(String::from(
+                "reading TOML migrations needs the toml feature",
+            ))),
+            _ => Self::from_json(&contents),
+        }
+    }
+
+    /// Compare this migration with a live schema, and list the changes needed to match it
+    ///
+    /// Steps are ordered so that they can be applied in one request: copy fields and fields are
+    /// deleted before the types they use, and types are added before the fields which use them.
+    pub fn plan(&self, live: &SolrSchema) -> SolrResult<SolrSchemaPlan> {
+        let mut plan = SolrSchemaPlan::default();
+        let protected =
+            |name: &str| name.starts_with('_') || live.unique_key.as_deref() == Some(name);
+        let copied = |name: &str| {
+            self.copy_fields
+                .iter()
+                .any(|c| c.source == name || c.dest == name)
+        };
+
+        // undeclared fields are pruned, unless a declared copy field still refers to them
+        let mut pruned = HashSet::new();
+        if self.prune {
+            for (declared, existing) in &[
+                (&self.fields, &live.fields),
+                (&self.dynamic_fields, &live.dynamic_fields),
+            ] {
+                for field in existing.iter() {
+                    let kept = declared.iter().any(|f| f.name == field.name);
+                    if !kept && !protected(&field.name) && !copied(&field.name) {
+                        pruned.insert(field.name.as_str());
+                    }
+                }
+            }
+        }
+
+        // copy fields are matched on source and dest, and re-added if anything else changes
+        let mut kept_copy_fields = HashSet::new();
+        for copy_field in &live.copy_fields {
+            let declared = self
+                .copy_fields
+                .iter()
+                .find(|c| c.source == copy_field.source && c.dest == copy_field.dest);
+            match declared {
+                Some(declared) if declared == copy_field => {
+                    kept_copy_fields.insert((&copy_field.source, &copy_field.dest));
+                }
+                Some(_) => plan.delete_copy_field(copy_field),
+                None if self.prune && !protected(&copy_field.dest) => {
+                    plan.delete_copy_field(copy_field)
+                }
+                // a copy field would stop its source or dest from being deleted
+                None if pruned.contains(copy_field.source.as_str())
+                    || pruned.contains(copy_field.dest.as_str()) =>
+                {
+                    plan.delete_copy_field(copy_field)
+                }
+                None => {
+                    kept_copy_fields.insert((&copy_field.source, &copy_field.dest));
+                }
+            }
+        }
+
+        let mut used_types = HashSet::new();
+        for (item, declared, existing) in &[
+            (SolrSchemaItem::Field, &self.fields, &live.fields),
+            (
+                SolrSchemaItem::DynamicField,
+                &self.dynamic_fields,
+                &live.dynamic_fields,
+            ),
+        ] {
+            for field in existing.iter() {
+                if pruned.contains(field.name.as_str()) {
+                    plan.delete(*item, &field.name);
+                } else if !declared.iter().any(|f| f.name == field.name) {
+                    used_types.insert(field.field_type.as_str());
+                }
+            }
+            for field in declared.iter() {
+                used_types.insert(field.field_type.as_str());
+            }
+        }
+
+        for field_type in &self.field_types {
+            match live.field_type(&field_type.name) {
+                None => plan.add(SolrSchemaItem::FieldType, &field_type.name, field_type)?,
+                Some(existing) if !same_definition(existing, field_type)? => plan.replace(
+                    SolrSchemaItem::FieldType,
+                    &field_type.name,
+                    field_type,
+                    type_needs_reindex(existing, field_type)?,
+                )?,
+                Some(_) => {}
+            }
+        }
+
+        for (item, declared, existing) in &[
+            (SolrSchemaItem::Field, &self.fields, &live.fields),
+            (
+                SolrSchemaItem::DynamicField,
+                &self.dynamic_fields,
+                &live.dynamic_fields,
+            ),
+        ] {
+            for field in declared.iter() {
+                match existing.iter().find(|f| f.name == field.name) {
+                    None => plan.add(*item, &field.name, field)?,
+                    Some(existing) if existing != field => plan.replace(
+                        *item,
+                        &field.name,
+                        field,
+                        field_needs_reindex(existing, field),
+                    )?,
+                    Some(_) => {}
+                }
+            }
+        }
+
+        for copy_field in &self.copy_fields {
+            if !kept_copy_fields.contains(&(&copy_field.source, &copy_field.dest)) {
+                plan.add(
+                    SolrSchemaItem::CopyField,
+                    &copy_field_name(copy_field),
+                    copy_field,
+                )?;
+            }
+        }
+
+        if self.prune {
+            for field_type in &live.field_types {
+                let declared = self.field_types.iter().any(|t| t.name == field_type.name);
+                if !declared && !used_types.contains(field_type.name.as_str()) {
+                    plan.delete(SolrSchemaItem::FieldType, &field_type.name);
+                }
+            }
+        }
+
+        Ok(plan)
+    }
+}
+
+impl From<SolrSchema> for SolrSchemaMigration {
+    /// Declare a whole schema, eg. one read from another collection
+    fn from(schema: SolrSchema) -> Self {
+        SolrSchemaMigration {
+            field_types: schema.field_types,
+            fields: schema.fields,
+            dynamic_fields: schema.dynamic_fields,
+            copy_fields: schema.copy_fields,
+            prune: false,
+            allow_reindex: false,
+        }
+    }
+}
+
+/// The kind of schema definition a step changes
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum SolrSchemaItem {
+    FieldType,
+    Field,
+    DynamicField,
+    CopyField,
+}
+
+impl fmt::Display for SolrSchemaItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolrSchemaItem::FieldType => write!(f, "field type"),
+            SolrSchemaItem::Field => write!(f, "field"),
+            SolrSchemaItem::DynamicField => write!(f, "dynamic field"),
+            SolrSchemaItem::CopyField => write!(f, "copy field"),
+        }
+    }
+}
+
+/// What a step does to its schema definition
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum SolrSchemaAction {
+    Add,
+    Replace,
+    Delete,
+}
+
+/// A single change within a schema plan
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SolrSchemaStep {
+    pub action: SolrSchemaAction,
+    pub item: SolrSchemaItem,
+    /// The definition's name (for copy fields, "source -> dest")
+    pub name: String,
+    /// Whether existing documents must be reindexed for the change to apply to them
+    pub requires_reindex: bool,
+    /// The Schema API command body for this step
+    pub definition: Value,
+}
+
+impl SolrSchemaStep {
+    /// The Schema API command which makes this change, eg. add-field
+    pub fn command(&self) -> &'static str {
+        match (self.action, self.item) {
+            (SolrSchemaAction::Add, SolrSchemaItem::FieldType) => "add-field-type",
+            (SolrSchemaAction::Add, SolrSchemaItem::Field) => "add-field",
+            (SolrSchemaAction::Add, SolrSchemaItem::DynamicField) => "add-dynamic-field",
+            (SolrSchemaAction::Add, SolrSchemaItem::CopyField) => "add-copy-field",
+            (SolrSchemaAction::Replace, SolrSchemaItem::FieldType) => "replace-field-type",
+            (SolrSchemaAction::Replace, SolrSchemaItem::Field) => "replace-field",
+            (SolrSchemaAction::Replace, SolrSchemaItem::DynamicField) => "replace-dynamic-field",
+            // copy fields have no replace command, so changes are planned as a delete then add
+            (SolrSchemaAction::Replace, SolrSchemaItem::CopyField) => "add-copy-field",
+            (SolrSchemaAction::Delete, SolrSchemaItem::FieldType) => "delete-field-type",
+            (SolrSchemaAction::Delete, SolrSchemaItem::Field) => "delete-field",
+            (SolrSchemaAction::Delete, SolrSchemaItem::DynamicField) => "delete-dynamic-field",
+            (SolrSchemaAction::Delete, SolrSchemaItem::CopyField) => "delete-copy-field",
+        }
+    }
+}
+
+impl fmt::Display for SolrSchemaStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let marker = match self.action {
+            SolrSchemaAction::Add => '+',
+            SolrSchemaAction::Replace => '~',
+            SolrSchemaAction::Delete => '-',
+        };
+        write!(f, "{} {} {}", marker, self.item, self.name)?;
+        if self.requires_reindex {
+            write!(f, " (requires reindex)")?;
+        }
+        Ok(())
+    }
+}
+
+/// The ordered list of changes needed to bring a live schema in line with a migration
+#[derive(Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+pub struct SolrSchemaPlan {
+    pub steps: Vec<SolrSchemaStep>,
+}
+
+impl SolrSchemaPlan {
+    /// Check whether the live schema already matches
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Check whether any step needs existing documents to be reindexed
+    pub fn requires_reindex(&self) -> bool {
+        self.steps.iter().any(|step| step.requires_reindex)
+    }
+
+    /// Build the single Schema API request which applies every step, in order
+    pub fn changes(&self) -> SolrSchemaChanges {
+        self.steps
+            .iter()
+            .fold(SolrSchemaChanges::new(), |changes, step| {
+                changes.push(step.command(), step.definition.clone())
+            })
+    }
+
+    fn add(
+        &mut self,
+        item: SolrSchemaItem,
+        name: &str,
+        definition: &impl Serialize,
+    ) -> SolrResult<()> {
+        let definition = serde_json::to_value(definition)?;
+        self.push(SolrSchemaAction::Add, item, name, definition, false);
+        Ok(())
+    }
+
+    fn replace(
+        &mut self,
+        item: SolrSchemaItem,
+        name: &str,
+        definition: &impl Serialize,
+        requires_reindex: bool,
+    ) -> SolrResult<()> {
+        let definition = serde_json::to_value(definition)?;
+        self.push(
+            SolrSchemaAction::Replace,
+            item,
+            name,
+            definition,
+            requires_reindex,
+        );
+        Ok(())
+    }
+
+    fn delete(&mut self, item: SolrSchemaItem, name: &str) {
+        self.push(
+            SolrSchemaAction::Delete,
+            item,
+            name,
+            json!({ "name": name }),
+            false,
+        );
+    }
+
+    fn delete_copy_field(&mut self, copy_field: &SolrCopyField) {
+        let definition = json!({ "source": copy_field.source, "dest": copy_field.dest });
+        let name = copy_field_name(copy_field);
+        self.push(
+            SolrSchemaAction::Delete,
+            SolrSchemaItem::CopyField,
+            &name,
+            definition,
+            false,
+        );
+    }
+
+    fn push(
+        &mut self,
+        action: SolrSchemaAction,
+        item: SolrSchemaItem,
+        name: &str,
+        definition: Value,
+        requires_reindex: bool,
+    ) {
+        self.steps.push(SolrSchemaStep {
+            action,
+            item,
+            name: String::from(name),
+            requires_reindex,
+            definition,
+        });
+    }
+}
+
+/// One line per step, eg. "+ field genre", or "no changes"
+impl fmt::Display for SolrSchemaPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.steps.is_empty() {
+            return writeln!(f, "no changes");
+        }
+        for step in &self.steps {
+            writeln!(f, "{}", step)?;
+        }
+        Ok(())
+    }
+}
+
+fn copy_field_name(copy_field: &SolrCopyField) -> String {
+    format!("{} -> {}", copy_field.source, copy_field.dest)
+}
+
+/// Solr reports most field type properties and analyzer arguments back as strings, so compare
+/// definitions with all scalars stringified
+fn same_definition(live: &impl Serialize, declared: &impl Serialize) -> SolrResult<bool> {
+    Ok(normalised(live)? == normalised(declared)?)
+}
+
+fn normalised(definition: &impl Serialize) -> SolrResult<Value> {
+    Ok(stringify(serde_json::to_value(definition)?))
+}
+
+fn stringify(value: Value) -> Value {
+    match value {
+        Value::Bool(b) => Value::String(b.to_string()),
+        Value::Number(n) => Value::String(n.to_string()),
+        Value::Array(values) => Value::Array(values.into_iter().map(stringify).collect()),
+        Value::Object(map) => {
+            Value::Object(map.into_iter().map(|(k, v)| (k, stringify(v))).collect())
+        }
+        other => other,
+    }
+}
+
+fn type_needs_reindex(live: &SolrFieldType, declared: &SolrFieldType) -> SolrResult<bool> {
+    // query-time analysis can change freely, so compare everything else
+    let index_side = |field_type: &SolrFieldType| SolrFieldType {
+        query_analyzer: None,
+        multi_term_analyzer: None,
+        ..field_type.clone()
+    };
+    same_definition(&index_side(live), &index_side(declared)).map(|same| !same)
+}
+
+fn field_needs_reindex(live: &SolrSchemaField, declared: &SolrSchemaField) -> bool {
+    // these properties are only read at query time
+    let index_side = |field: &SolrSchemaField| SolrSchemaField {
+        default: None,
+        required: None,
+        uninvertible: None,
+        use_doc_values_as_stored: None,
+        sort_missing_first: None,
+        sort_missing_last: None,
+        ..field.clone()
+    };
+    index_side(live) != index_side(declared)
+}
+
+/// Compare a migration with a core's live schema, without changing anything (a dry run)
+#[cfg(not(feature = "blocking"))]
+pub async fn plan_schema_migration<C: SolrCoreMethods>(
+    client: &C,
+    core: &str,
+    migration: &SolrSchemaMigration,
+) -> SolrResult<SolrSchemaPlan> {
+    let live = client.schema(core)?.call::<SolrSchemaType>().await?;
+    migration.plan(&live.schema)
+}
+
+/// Compare a migration with a core's live schema, without changing anything (a dry run)
+#[cfg(feature = "blocking")]
+pub fn plan_schema_migration<C: SolrCoreMethods>(
+    client: &C,
+    core: &str,
+    migration: &SolrSchemaMigration,
+) -> SolrResult<SolrSchemaPlan> {
+    let live = client.schema(core)?.call::<SolrSchemaType>()?;
+    migration.plan(&live.schema)
+}
+
+/// Plan a migration against a core's live schema, then apply the whole plan in one request
+///
+/// Plans needing a reindex are refused unless the migration sets allow_reindex. The applied plan
+/// is returned, and is empty if the schema already matched.
+#[cfg(not(feature = "blocking"))]
+pub async fn apply_schema_migration<C: SolrCoreMethods>(
+    client: &C,
+    core: &str,
+    migration: &SolrSchemaMigration,
+) -> SolrResult<SolrSchemaPlan> {
+    let plan = plan_schema_migration(client, core, migration).await?;
+    check_plan(&plan, migration)?;
+    if !plan.is_empty() {
+        let response = client
+            .update_schema(core, &plan.changes())?
+            .call::<SolrUpdateType>()
+            .await?;
+        check_response(&response)?;
+    }
+    Ok(plan)
+}
+
+/// Plan a migration against a core's live schema, then apply the whole plan in one request
+///
+/// Plans needing a reindex are refused unless the migration sets allow_reindex. The applied plan
+/// is returned, and is empty if the schema already matched.
+#[cfg(feature = "blocking")]
+pub fn apply_schema_migration<C: SolrCoreMethods>(
+    client: &C,
+    core: &str,
+    migration: &SolrSchemaMigration,
+) -> SolrResult<SolrSchemaPlan> {
+    let plan = plan_schema_migration(client, core, migration)?;
+    check_plan(&plan, migration)?;
+    if !plan.is_empty() {
+        let response = client
+            .update_schema(core, &plan.changes())?
+            .call::<SolrUpdateType>()?;
+        check_response(&response)?;
+    }
+    Ok(plan)
+}
+
+fn check_plan(plan: &SolrSchemaPlan, migration: &SolrSchemaMigration) -> SolrResult<()> {
+    if plan.requires_reindex() && !migration.allow_reindex {
+        let steps: Vec<String> = plan
+            .steps
+            .iter()
+            .filter(|step| step.requires_reindex)
+            .map(|step| step.to_string())
+            .collect();
+        return Err(SolrError::SchemaError(format!(
+            "changes need a reindex, and allow_reindex is not set: {}",
+            steps.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+fn check_response(response: &SolrUpdateType) -> SolrResult<()> {
+    match &response.error {
+        None => Ok(()),
+        Some(error) => {
+            let mut message = error.msg.clone().unwrap_or_default();
+            for detail in error.details.iter().flatten() {
+                for reason in detail["errorMessages"].as_array().into_iter().flatten() {
+                    message.push_str(&format!("; {}", reason.as_str().unwrap_or_default().trim()));
+                }
+            }
+            Err(SolrError::SchemaError(message))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn live_schema() -> SolrSchema {
+        serde_json::from_value(json!({
+            "uniqueKey": "id",
+            "fieldTypes": [
+                {"name": "string", "class": "solr.StrField", "sortMissingLast": true},
+                {"name": "pdate", "class": "solr.DatePointField", "docValues": true},
+                {"name": "text_general", "class": "solr.TextField", "positionIncrementGap": "100",
+                 "analyzer": {"tokenizer": {"class": "solr.StandardTokenizerFactory"}}}
+            ],
+            "fields": [
+                {"name": "id", "type": "string", "required": true, "stored": true},
+                {"name": "_version_", "type": "plong", "indexed": false, "stored": false},
+                {"name": "name", "type": "text_general", "stored": true},
+                {"name": "directed_by", "type": "string", "multiValued": true}
+            ],
+            "copyFields": [{"source": "name", "dest": "_text_"}, {"source": "directed_by", "dest": "text"}]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_plan_orders_changes() {
+        let migration = SolrSchemaMigration::from_json(
+            r#"{
+                "prune": true,
+                "fieldTypes": [
+                    {"name": "string", "class": "solr.StrField", "sortMissingLast": "true"},
+                    {"name": "text_general", "class": "solr.TextField", "positionIncrementGap": 100,
+                     "analyzer": {"tokenizer": {"class": "solr.StandardTokenizerFactory"}}},
+                    {"name": "pint", "class": "solr.IntPointField", "docValues": true}
+                ],
+                "fields": [
+                    {"name": "name", "type": "text_general", "stored": true},
+                    {"name": "genre", "type": "string", "multiValued": true},
+                    {"name": "year", "type": "pint"}
+                ],
+                "copyFields": [{"source": "name", "dest": "_text_"}, {"source": "genre", "dest": "_text_"}]
+            }"#,
+        )
+        .unwrap();
+
+        let plan = migration.plan(&live_schema()).unwrap();
+        assert_eq!(
+            plan.to_string(),
+            "- copy field directed_by -> text\n\
+             - field directed_by\n\
+             + field type pint\n\
+             + field genre\n\
+             + field year\n\
+             + copy field genre -> _text_\n\
+             - field type pdate\n"
+        );
+        assert!(!plan.requires_reindex());
+
+        let changes = plan.changes().to_string();
+        assert!(changes.starts_with(
+            r#"{"delete-copy-field":{"dest":"text","source":"directed_by"},"delete-field":{"name":"directed_by"},"add-field-type":"#
+        ));
+    }
+
+    #[test]
+    fn test_plan_prunes_copy_fields_of_pruned_fields() {
+        let mut live = live_schema();
+        live.fields
+            .push(SolrSchemaField::new("title", "text_general"));
+        live.copy_fields.push(SolrCopyField::new("title", "_text_"));
+        let mut migration: SolrSchemaMigration = live_schema().into();
+        migration.prune = true;
+        migration.fields.retain(|f| f.name != "directed_by");
+
+        // directed_by is still the source of a declared copy field, so is kept
+        let plan = migration.plan(&live).unwrap();
+        assert_eq!(
+            plan.to_string(),
+            "- copy field title -> _text_\n\
+             - field title\n"
+        );
+    }
+
+    #[test]
+    fn test_plan_flags_reindex() {
+        let migration = SolrSchemaMigration {
+            fields: vec![
+                SolrSchemaField {
+                    stored: Some(true),
+                    ..SolrSchemaField::new("name", "string")
+                },
+                SolrSchemaField {
+                    multi_valued: Some(true),
+                    required: Some(true),
+                    ..SolrSchemaField::new("directed_by", "string")
+                },
+            ],
+            ..Default::default()
+        };
+
+        let plan = migration.plan(&live_schema()).unwrap();
+        assert_eq!(
+            plan.to_string(),
+            "~ field name (requires reindex)\n~ field directed_by\n"
+        );
+        assert!(check_plan(&plan, &migration).is_err());
+        assert!(migration.plan(&live_schema()).unwrap().steps[1].definition["required"] == true);
+
+        let unchanged: SolrSchemaMigration = live_schema().into();
+        assert!(unchanged.plan(&live_schema()).unwrap().is_empty());
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_from_toml() {
+        let migration = SolrSchemaMigration::from_toml(
+            r#"
+            prune = true
+
+            [[fields]]
+            name = "genre"
+            type = "string"
+            multiValued = true
+
+            [[copyFields]]
+            source = "genre"
+            dest = "_text_"
+            "#,
+        )
+        .unwrap();
+
+        assert!(migration.prune);
+        assert_eq!(migration.fields[0].multi_valued, Some(true));
+        assert_eq!(
+            migration.copy_fields[0],
+            SolrCopyField::new("genre", "_text_")
+        );
+    }
+}
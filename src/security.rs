@@ -0,0 +1,412 @@
+//! Security API model, and authentication/authorization commands
+//!
+//! The structs here match the contents of security.json, as returned by `GET /admin/authentication`
+//! and `GET /admin/authorization` (deserialised with `response_types::SolrAuthenticationType` and
+//! `response_types::SolrAuthorizationType`), or read straight from zookeeper with
+//! `ZkSolrClient::read_security_config`.
+//!
+//! Changes are made with `SolrAuthenticationCommands` (BasicAuth users) and
+//! `SolrAuthorizationCommands` (rule-based user roles and permissions), sent with
+//! `SolrCoreMethods::update_authentication` and `SolrCoreMethods::update_authorization`.
+//!
+//! ```no_run
+//! # use tokio::runtime::Runtime;
+//! use stellr::prelude::*;
+//! use stellr::response_types::SolrUpdateType;
+//! use stellr::security::{SolrAuthenticationCommands, SolrAuthorizationCommands, SolrPermission};
+//!
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! let users = SolrAuthenticationCommands::new().set_user("indexer", "Correct-Horse-7");
+//! let roles = SolrAuthorizationCommands::new()
+//!     .set_user_role("indexer", &["update"])
+//!     .set_permission(SolrPermission::predefined("update", &["update", "admin"]))?;
+//!
+//! let solr_client = stellr::DirectSolrClient::new("http://localhost:8983/solr")?;
+//!
+//! # let mut rt = Runtime::new().unwrap();
+//! # rt.block_on(async {
+//! solr_client
+//!     .update_authentication(&users)?
+//!     .call::<SolrUpdateType>()
+//!     .await?;
+//! solr_client
+//!     .update_authorization(&roles)?
+//!     .call::<SolrUpdateType>()
+//!     .await?;
+//! # Ok::<(), stellr::SolrError>(())
+//! # })?;
+//! # Ok(()) }
+//! ```
+
+use serde::de::Deserializer;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::errors::SolrResult;
+use crate::requests::update_commands::SolrCommandList;
+use crate::serde_helpers::one_or_many;
+
+/// The whole of security.json
+///
+/// Any other top level sections (eg. auditlogging) are kept in other, so are preserved when the
+/// config is written back to zookeeper.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+pub struct SolrSecurityConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authentication: Option<SolrAuthenticationConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorization: Option<SolrAuthorizationConfig>,
+    #[serde(flatten)]
+    pub other: BTreeMap<String, Value>,
+}
+
+/// The authentication section of security.json
+///
+/// For BasicAuth, credentials map user names to their salted password hash (not the password
+/// itself), so users are best added with `SolrAuthenticationCommands::set_user`. Settings for
+/// other plugins are kept in other.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SolrAuthenticationConfig {
+    pub class: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_unknown: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub realm: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forward_credentials: Option<bool>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub credentials: BTreeMap<String, String>,
+    #[serde(flatten)]
+    pub other: BTreeMap<String, Value>,
+}
+
+impl SolrAuthenticationConfig {
+    pub fn has_user(&self, user: &str) -> bool {
+        self.credentials.contains_key(user)
+    }
+}
+
+/// The authorization section of security.json
+#[derive(Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+pub struct SolrAuthorizationConfig {
+    pub class: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub permissions: Vec<SolrPermission>,
+    /// Roles held by each user
+    #[serde(
+        rename = "user-role",
+        default,
+        deserialize_with = "deserialize_user_roles",
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub user_role: BTreeMap<String, Vec<String>>,
+    #[serde(flatten)]
+    pub other: BTreeMap<String, Value>,
+}
+
+impl SolrAuthorizationConfig {
+    /// Find a permission by name, eg. "read" or a custom permission's name
+    pub fn permission(&self, name: &str) -> Option<&SolrPermission> {
+        self.permissions
+            .iter()
+            .find(|permission| permission.name.as_deref() == Some(name))
+    }
+
+    /// List the roles held by a user, which is empty for unknown users
+    pub fn roles(&self, user: &str) -> &[String] {
+        self.user_role
+            .get(user)
+            .map(|roles| roles.as_slice())
+            .unwrap_or_default()
+    }
+}
+
+/// A single rule-based authorization permission
+///
+/// Predefined permissions (eg. read, update, all) only need a name and role. Custom permissions
+/// match requests on collection, path, method and params instead. Permissions are checked in
+/// order, and the first match applies.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+pub struct SolrPermission {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Collections to match: None leaves this unset (matching any collection), Some(None) is
+    /// written as null, matching requests which aren't for a collection (eg. /admin/collections)
+    #[serde(
+        default,
+        deserialize_with = "deserialize_nullable_list",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub collection: Option<Option<Vec<String>>>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_optional_list",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub path: Option<Vec<String>>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_optional_list",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub method: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params: Option<BTreeMap<String, Value>>,
+    /// Roles allowed by this permission: None leaves this unset (which solr rejects), Some(None)
+    /// is written as null, allowing any request (even unauthenticated, eg. for health checks)
+    #[serde(
+        default,
+        deserialize_with = "deserialize_nullable_list",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub role: Option<Option<Vec<String>>>,
+    /// Position of this permission, as reported by solr (used by update-permission)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index: Option<u32>,
+    /// Insert this permission before the one at this index, rather than at the end
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<u32>,
+}
+
+impl SolrPermission {
+    /// One of solr's predefined permissions, eg. "read", "update" or "security-edit"
+    pub fn predefined(name: &str, roles: &[&str]) -> SolrPermission {
+        SolrPermission {
+            name: Some(String::from(name)),
+            role: Some(Some(roles.iter().map(|role| String::from(*role)).collect())),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct OneOrMany(#[serde(with = "one_or_many")] Vec<String>);
+
+fn deserialize_optional_list<'de, D>(deserializer: D) -> Result<Option<Vec<String>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::<OneOrMany>::deserialize(deserializer)?.map(|list| list.0))
+}
+
+// only called when the key is present, so null becomes Some(None)
+fn deserialize_nullable_list<'de, D>(
+    deserializer: D,
+) -> Result<Option<Option<Vec<String>>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserialize_optional_list(deserializer).map(Some)
+}
+
+fn deserialize_user_roles<'de, D>(
+    deserializer: D,
+) -> Result<BTreeMap<String, Vec<String>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(
+        Option::<BTreeMap<String, Option<OneOrMany>>>::deserialize(deserializer)?
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(user, roles)| (user, roles.map(|roles| roles.0).unwrap_or_default()))
+            .collect(),
+    )
+}
+
+/// Builder for a BasicAuth /admin/authentication request holding several commands
+#[derive(Serialize, Clone, Debug, Default, PartialEq)]
+#[serde(transparent)]
+pub struct SolrAuthenticationCommands {
+    commands: SolrCommandList,
+}
+
+impl SolrAuthenticationCommands {
+    pub fn new() -> SolrAuthenticationCommands {
+        SolrAuthenticationCommands::default()
+    }
+
+    /// Add a user, or change an existing user's password
+    pub fn set_user(self, user: &str, password: &str) -> SolrAuthenticationCommands {
+        self.push("set-user", json!({ user: password }))
+    }
+
+    pub fn delete_user(self, user: &str) -> SolrAuthenticationCommands {
+        self.push("delete-user", json!([user]))
+    }
+
+    /// Change a plugin setting, eg. blockUnknown or forwardCredentials
+    pub fn set_property(self, name: &str, value: impl Into<Value>) -> SolrAuthenticationCommands {
+        self.push("set-property", json!({ name: value.into() }))
+    }
+
+    fn push(mut self, command: &'static str, value: Value) -> SolrAuthenticationCommands {
+        self.commands.push(command, value);
+        self
+    }
+}
+
+/// Builder for a rule-based /admin/authorization request holding several commands
+#[derive(Serialize, Clone, Debug, Default, PartialEq)]
+#[serde(transparent)]
+pub struct SolrAuthorizationCommands {
+    commands: SolrCommandList,
+}
+
+impl SolrAuthorizationCommands {
+    pub fn new() -> SolrAuthorizationCommands {
+        SolrAuthorizationCommands::default()
+    }
+
+    /// Replace a user's roles, where an empty list removes the user's roles altogether
+    pub fn set_user_role(self, user: &str, roles: &[&str]) -> SolrAuthorizationCommands {
+        let roles = if roles.is_empty() {
+            Value::Null
+        } else {
+            json!(roles)
+        };
+        self.push("set-user-role", json!({ user: roles }))
+    }
+
+    /// Add a permission, at the end of the list unless its before index is set
+    pub fn set_permission(
+        self,
+        permission: SolrPermission,
+    ) -> SolrResult<SolrAuthorizationCommands> {
+        self.push_permission("set-permission", permission)
+    }
+
+    /// Replace the permission at an index (as listed by solr, starting from 1)
+    pub fn update_permission(
+        self,
+        index: u32,
+        permission: SolrPermission,
+    ) -> SolrResult<SolrAuthorizationCommands> {
+        self.push_permission(
+            "update-permission",
+            SolrPermission {
+                index: Some(index),
+                ..permission
+            },
+        )
+    }
+
+    /// Remove the permission at an index (as listed by solr, starting from 1)
+    pub fn delete_permission(self, index: u32) -> SolrAuthorizationCommands {
+        self.push("delete-permission", json!(index))
+    }
+
+    fn push_permission(
+        mut self,
+        command: &'static str,
+        permission: SolrPermission,
+    ) -> SolrResult<SolrAuthorizationCommands> {
+        self.commands.push_serialized(command, &permission)?;
+        Ok(self)
+    }
+
+    fn push(mut self, command: &'static str, value: Value) -> SolrAuthorizationCommands {
+        self.commands.push(command, value);
+        self
+    }
+}
+
+impl fmt::Display for SolrAuthenticationCommands {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.commands.fmt(f)
+    }
+}
+
+impl fmt::Display for SolrAuthorizationCommands {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.commands.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_security_config_round_trip() {
+        let raw = json!({
+            "authentication": {
+                "class": "solr.BasicAuthPlugin",
+                "blockUnknown": true,
+                "credentials": {"solr": "IV0EHq1OnNrj6gvRCwvFwTrZ1+z1oBbnQdiVC3otuq0= Ndd7LKvVBAaZIF0QAVi1ekCfAJXr1GGfLtRUXhgrF8c="},
+                "": {"v": 4}
+            },
+            "authorization": {
+                "class": "solr.RuleBasedAuthorizationPlugin",
+                "permissions": [
+                    {"name": "read", "role": "*", "index": 1},
+                    {"name": "collection-admin", "collection": null, "path": "/admin/collections", "role": ["admin"], "index": 2},
+                    {"name": "health", "path": "/admin/info/health", "role": null, "index": 3}
+                ],
+                "user-role": {"solr": "admin", "indexer": ["update", "read"]}
+            },
+            "auditlogging": {"class": "solr.SolrLogAuditLoggerPlugin"}
+        });
+        let config: SolrSecurityConfig = serde_json::from_value(raw.clone()).unwrap();
+
+        let authentication = config.authentication.as_ref().unwrap();
+        assert_eq!(authentication.block_unknown, Some(true));
+        assert!(authentication.has_user("solr"));
+        assert_eq!(authentication.other[""], json!({"v": 4}));
+
+        let authorization = config.authorization.as_ref().unwrap();
+        assert_eq!(authorization.roles("solr"), ["admin"]);
+        assert_eq!(authorization.roles("indexer"), ["update", "read"]);
+        assert!(authorization.roles("nobody").is_empty());
+        assert_eq!(authorization.permission("read").unwrap().collection, None);
+        assert_eq!(
+            authorization
+                .permission("collection-admin")
+                .unwrap()
+                .collection,
+            Some(None)
+        );
+        assert_eq!(authorization.permission("health").unwrap().role, Some(None));
+
+        let mut expected = raw;
+        expected["authorization"]["permissions"][0]["role"] = json!(["*"]);
+        expected["authorization"]["permissions"][2]["path"] = json!(["/admin/info/health"]);
+        expected["authorization"]["permissions"][1]["path"] = json!(["/admin/collections"]);
+        expected["authorization"]["user-role"]["solr"] = json!(["admin"]);
+        assert_eq!(serde_json::to_value(&config).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_security_commands_body() {
+        let users = SolrAuthenticationCommands::new()
+            .set_user("indexer", "secret")
+            .delete_user("old")
+            .set_property("blockUnknown", true);
+        assert_eq!(
+            users.to_string(),
+            r#"{"set-user":{"indexer":"secret"},"delete-user":["old"],"set-property":{"blockUnknown":true}}"#
+        );
+
+        let roles = SolrAuthorizationCommands::new()
+            .set_user_role("indexer", &["update"])
+            .set_user_role("old", &[])
+            .set_permission(SolrPermission {
+                collection: Some(None),
+                path: Some(vec![String::from("/admin/collections")]),
+                before: Some(1),
+                ..SolrPermission::predefined("collections", &["admin"])
+            })
+            .unwrap()
+            .update_permission(2, SolrPermission::predefined("read", &["read"]))
+            .unwrap()
+            .delete_permission(3);
+        assert_eq!(
+            roles.to_string(),
+            r#"{"set-user-role":{"indexer":["update"]},"set-user-role":{"old":null},"set-permission":{"before":1,"collection":null,"name":"collections","path":["/admin/collections"],"role":["admin"]},"update-permission":{"index":2,"name":"read","role":["read"]},"delete-permission":3}"#
+        );
+    }
+}
@@ -0,0 +1,581 @@
+//! Serde helpers for solr-specific value formats
+//!
+//! These are intended for use on the fields of user-defined document structs, which are then
+//! passed to `SolrRequest::call` (or used as an update payload), eg.
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use stellr::serde_helpers::{one_or_many, version, SolrCurrency, SolrPoint};
+//!
+//! #[derive(Debug, Serialize, Deserialize)]
+//! struct Store {
+//!     id: String,
+//!     #[serde(with = "one_or_many")]
+//!     directed_by: Vec<String>,
+//!     store: SolrPoint,
+//!     price: SolrCurrency,
+//!     #[serde(rename = "_version_", with = "version")]
+//!     version: i64,
+//! }
+//!
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! let store: Store = serde_json::from_str(r#"{
+//!     "id": "/en/45_2006",
+//!     "directed_by": "Gary Lennon",
+//!     "store": "45.17614,-93.87341",
+//!     "price": "10.00,USD",
+//!     "_version_": 1683174404568121344
+//! }"#)?;
+//!
+//! assert_eq!(store.directed_by, vec!["Gary Lennon"]);
+//! assert_eq!(store.store.lat, 45.17614);
+//! assert_eq!(store.price.code, "USD");
+//! # Ok(()) }
+//! ```
+//!
+//! Solr dates can be converted to chrono or time types, using the `chrono_date` and `time_date`
+//! modules, which require the `chrono` and `time` features respectively.
+
+use serde::de::{self, DeserializeOwned};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// Accept either a single value or a list of values for a Vec field
+///
+/// Solr returns a bare value for single-valued fields, and a list for multi-valued ones, so this
+/// is useful where a field's type differs between collections or schema versions. Values are
+/// always serialized as a list.
+pub mod one_or_many {
+    use super::*;
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany<T> {
+        Many(Vec<T>),
+        One(T),
+    }
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: DeserializeOwned,
+    {
+        match OneOrMany::<T>::deserialize(deserializer)? {
+            OneOrMany::Many(values) => Ok(values),
+            OneOrMany::One(value) => Ok(vec![value]),
+        }
+    }
+
+    pub fn serialize<S, T>(values: &[T], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: Serialize,
+    {
+        values.serialize(serializer)
+    }
+}
+
+/// Read a `_version_` field as an i64, whether solr returns it as a number or a string
+pub mod version {
+    use super::*;
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum NumberOrString {
+        Number(i64),
+        String(String),
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<i64, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match NumberOrString::deserialize(deserializer)? {
+            NumberOrString::Number(version) => Ok(version),
+            NumberOrString::String(version) => version.parse().map_err(de::Error::custom),
+        }
+    }
+
+    pub fn serialize<S>(version: &i64, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_i64(*version)
+    }
+}
+
+/// Convert solr dates (eg. `2006-11-30T00:00:00Z`) to and from chrono's `DateTime<Utc>`
+#[cfg(feature = "chrono")]
+pub mod chrono_date {
+    use super::*;
+    use chrono::{DateTime, TimeZone, Utc};
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let text = String::deserialize(deserializer)?;
+        let (seconds, nanos) = parse_solr_date(&text).map_err(de::Error::custom)?;
+        Utc.timestamp_opt(seconds, nanos)
+            .single()
+            .ok_or_else(|| de::Error::custom(format!("date out of range: {}", text)))
+    }
+
+    pub fn serialize<S>(date: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let text = format_solr_date(date.timestamp(), date.timestamp_subsec_nanos());
+        serializer.serialize_str(&text)
+    }
+
+    /// As chrono_date, for `Option<DateTime<Utc>>` fields
+    pub mod optional {
+        use super::*;
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            #[derive(Deserialize)]
+            struct Wrapper(#[serde(deserialize_with = "super::deserialize")] DateTime<Utc>);
+
+            Ok(Option::<Wrapper>::deserialize(deserializer)?.map(|wrapper| wrapper.0))
+        }
+
+        pub fn serialize<S>(date: &Option<DateTime<Utc>>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            match date {
+                Some(date) => super::serialize(date, serializer),
+                None => serializer.serialize_none(),
+            }
+        }
+    }
+}
+
+/// Convert solr dates (eg. `2006-11-30T00:00:00Z`) to and from time's `OffsetDateTime`
+#[cfg(feature = "time")]
+pub mod time_date {
+    use super::*;
+    use time::{Duration, OffsetDateTime, UtcOffset};
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<OffsetDateTime, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let text = String::deserialize(deserializer)?;
+        let (seconds, nanos) = parse_solr_date(&text).map_err(de::Error::custom)?;
+        Ok(OffsetDateTime::from_unix_timestamp(seconds) + Duration::nanoseconds(nanos.into()))
+    }
+
+    pub fn serialize<S>(date: &OffsetDateTime, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let utc_date = date.to_offset(UtcOffset::UTC);
+        let text = format_solr_date(utc_date.unix_timestamp(), utc_date.nanosecond());
+        serializer.serialize_str(&text)
+    }
+
+    /// As time_date, for `Option<OffsetDateTime>` fields
+    pub mod optional {
+        use super::*;
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<OffsetDateTime>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            #[derive(Deserialize)]
+            struct Wrapper(#[serde(deserialize_with = "super::deserialize")] OffsetDateTime);
+
+            Ok(Option::<Wrapper>::deserialize(deserializer)?.map(|wrapper| wrapper.0))
+        }
+
+        pub fn serialize<S>(date: &Option<OffsetDateTime>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            match date {
+                Some(date) => super::serialize(date, serializer),
+                None => serializer.serialize_none(),
+            }
+        }
+    }
+}
+
+/// A value of a solr currency field, eg. `10.00,USD`
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub struct SolrCurrency {
+    pub amount: f64,
+    /// ISO 4217 currency code
+    pub code: String,
+}
+
+impl fmt::Display for SolrCurrency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.amount, self.code)
+    }
+}
+
+impl FromStr for SolrCurrency {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut parts = text.splitn(2, ',');
+        let amount = parts.next().unwrap_or_default().trim();
+        let amount = amount
+            .parse()
+            .map_err(|_| format!("invalid currency amount: {}", text))?;
+        let code = match parts.next() {
+            Some(code) => code.trim().to_string(),
+            None => return Err(format!("missing currency code: {}", text)),
+        };
+        Ok(SolrCurrency { amount, code })
+    }
+}
+
+/// A value of a solr LatLonPointSpatialField, eg. `45.17614,-93.87341`
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct SolrPoint {
+    pub lat: f64,
+    pub lon: f64,
+}
+
+impl fmt::Display for SolrPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.lat, self.lon)
+    }
+}
+
+impl FromStr for SolrPoint {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let coordinates: Vec<&str> = text.split(',').map(str::trim).collect();
+        match coordinates.as_slice() {
+            [lat, lon] => Ok(SolrPoint {
+                lat: lat
+                    .parse()
+                    .map_err(|_| format!("invalid latitude: {}", text))?,
+                lon: lon
+                    .parse()
+                    .map_err(|_| format!("invalid longitude: {}", text))?,
+            }),
+            _ => Err(format!("invalid point: {}", text)),
+        }
+    }
+}
+
+/// Units used in solr date math expressions
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum SolrDateUnit {
+    Year,
+    Month,
+    Day,
+    Hour,
+    Minute,
+    Second,
+    Millisecond,
+}
+
+impl fmt::Display for SolrDateUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let unit = match self {
+            SolrDateUnit::Year => "YEAR",
+            SolrDateUnit::Month => "MONTH",
+            SolrDateUnit::Day => "DAY",
+            SolrDateUnit::Hour => "HOUR",
+            SolrDateUnit::Minute => "MINUTE",
+            SolrDateUnit::Second => "SECOND",
+            SolrDateUnit::Millisecond => "MILLISECOND",
+        };
+        write!(f, "{}", unit)
+    }
+}
+
+impl FromStr for SolrDateUnit {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text {
+            "YEAR" | "YEARS" => Ok(SolrDateUnit::Year),
+            "MONTH" | "MONTHS" => Ok(SolrDateUnit::Month),
+            "DAY" | "DAYS" | "DATE" => Ok(SolrDateUnit::Day),
+            "HOUR" | "HOURS" => Ok(SolrDateUnit::Hour),
+            "MINUTE" | "MINUTES" => Ok(SolrDateUnit::Minute),
+            "SECOND" | "SECONDS" => Ok(SolrDateUnit::Second),
+            "MILLI" | "MILLIS" | "MILLISECOND" | "MILLISECONDS" => Ok(SolrDateUnit::Millisecond),
+            _ => Err(format!("unknown date math unit: {}", text)),
+        }
+    }
+}
+
+/// A single step in a solr date math expression
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum SolrDateMathOp {
+    /// Add (or with a negative amount, subtract) a number of units, eg. `+1DAY`
+    Add(i64, SolrDateUnit),
+    /// Round down to the start of a unit, eg. `/DAY`
+    Round(SolrDateUnit),
+}
+
+/// A solr date math expression, eg. `NOW/DAY-7DAYS`
+///
+/// This can be built up from NOW or a fixed date, and is serialized as (and parsed from) solr's
+/// date math syntax, so can be used in document fields or formatted into queries.
+///
+/// ```
+/// use stellr::serde_helpers::{SolrDateMath, SolrDateUnit};
+///
+/// let last_week = SolrDateMath::now()
+///     .round(SolrDateUnit::Day)
+///     .add(-7, SolrDateUnit::Day);
+/// assert_eq!(last_week.to_string(), "NOW/DAY-7DAY");
+/// assert_eq!("NOW/DAY-7DAYS".parse::<SolrDateMath>(), Ok(last_week));
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SolrDateMath {
+    /// The starting date, or None for NOW
+    pub anchor: Option<String>,
+    pub ops: Vec<SolrDateMathOp>,
+}
+
+impl SolrDateMath {
+    /// Start an expression from the current time
+    pub fn now() -> SolrDateMath {
+        SolrDateMath {
+            anchor: None,
+            ops: Vec::new(),
+        }
+    }
+
+    /// Start an expression from a fixed solr date, eg. `2006-11-30T00:00:00Z`
+    pub fn from_date(date: &str) -> SolrDateMath {
+        SolrDateMath {
+            anchor: Some(date.to_string()),
+            ops: Vec::new(),
+        }
+    }
+
+    /// Add (or with a negative amount, subtract) a number of units
+    pub fn add(mut self, amount: i64, unit: SolrDateUnit) -> SolrDateMath {
+        self.ops.push(SolrDateMathOp::Add(amount, unit));
+        self
+    }
+
+    /// Round down to the start of a unit
+    pub fn round(mut self, unit: SolrDateUnit) -> SolrDateMath {
+        self.ops.push(SolrDateMathOp::Round(unit));
+        self
+    }
+}
+
+impl fmt::Display for SolrDateMath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.anchor.as_deref().unwrap_or("NOW"))?;
+        for op in &self.ops {
+            match op {
+                SolrDateMathOp::Add(amount, unit) => write!(f, "{:+}{}", amount, unit)?,
+                SolrDateMathOp::Round(unit) => write!(f, "/{}", unit)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for SolrDateMath {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let (anchor, mut rest) = if let Some(rest) = text.strip_prefix("NOW") {
+            (None, rest)
+        } else {
+            let end = text
+                .find('Z')
+                .ok_or_else(|| format!("invalid date math: {}", text))?;
+            parse_solr_date(&text[..=end])?;
+            (Some(text[..=end].to_string()), &text[end + 1..])
+        };
+
+        let mut ops = Vec::new();
+        while !rest.is_empty() {
+            let (op, remainder) = rest.split_at(1);
+            let unit_start = remainder
+                .find(|c: char| c.is_ascii_alphabetic())
+                .ok_or_else(|| format!("missing date math unit: {}", text))?;
+            let unit_end = remainder[unit_start..]
+                .find(|c: char| !c.is_ascii_alphabetic())
+                .map_or(remainder.len(), |end| unit_start + end);
+            let unit = remainder[unit_start..unit_end].parse()?;
+
+            ops.push(match op {
+                "/" if unit_start == 0 => SolrDateMathOp::Round(unit),
+                "+" | "-" => {
+                    let amount: i64 = remainder[..unit_start]
+                        .parse()
+                        .map_err(|_| format!("invalid date math amount: {}", text))?;
+                    SolrDateMathOp::Add(if op == "-" { -amount } else { amount }, unit)
+                }
+                _ => return Err(format!("invalid date math: {}", text)),
+            });
+            rest = &remainder[unit_end..];
+        }
+
+        Ok(SolrDateMath { anchor, ops })
+    }
+}
+
+/// Implement Serialize and Deserialize using a type's Display and FromStr implementations
+macro_rules! string_serde {
+    ($type:ty) => {
+        impl Serialize for $type {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $type {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                String::deserialize(deserializer)?
+                    .parse()
+                    .map_err(de::Error::custom)
+            }
+        }
+    };
+}
+
+string_serde!(SolrCurrency);
+string_serde!(SolrPoint);
+string_serde!(SolrDateMath);
+
+/// Parse a solr date (eg. `2006-11-30T00:00:00Z` or `2006-11-30T00:00:00.123Z`) into seconds and
+/// nanoseconds since the unix epoch
+fn parse_solr_date(text: &str) -> Result<(i64, u32), String> {
+    let invalid = || format!("invalid solr date: {}", text);
+
+    let body = text.strip_suffix('Z').ok_or_else(invalid)?;
+    let (date, time) = match body.find('T') {
+        Some(split) => (&body[..split], &body[split + 1..]),
+        None => return Err(invalid()),
+    };
+    let (time, fraction) = match time.find('.') {
+        Some(split) => (&time[..split], &time[split + 1..]),
+        None => (time, ""),
+    };
+
+    let date: Vec<&str> = date.split('-').collect();
+    let time: Vec<&str> = time.split(':').collect();
+    if date.len() != 3 || time.len() != 3 || fraction.len() > 9 {
+        return Err(invalid());
+    }
+
+    let number = |part: &str| part.parse::<i64>().map_err(|_| invalid());
+    let (year, month, day) = (number(date[0])?, number(date[1])?, number(date[2])?);
+    let (hour, minute, second) = (number(time[0])?, number(time[1])?, number(time[2])?);
+    if !(1..=12).contains(&month)
+        || !(1..=31).contains(&day)
+        || hour > 23
+        || minute > 59
+        || second > 59
+    {
+        return Err(invalid());
+    }
+
+    let nanos = if fraction.is_empty() {
+        0
+    } else {
+        format!("{:0<9}", fraction).parse().map_err(|_| invalid())?
+    };
+
+    let days = days_from_civil(year, month, day);
+    Ok((days * 86400 + hour * 3600 + minute * 60 + second, nanos))
+}
+
+/// Format seconds and nanoseconds since the unix epoch as a solr date
+#[cfg_attr(not(any(feature = "chrono", feature = "time")), allow(dead_code))]
+fn format_solr_date(timestamp: i64, nanos: u32) -> String {
+    let (year, month, day) = civil_from_days(timestamp.div_euclid(86400));
+    let seconds = timestamp.rem_euclid(86400);
+
+    let mut text = format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    );
+    if nanos > 0 {
+        text.push_str(format!(".{:09}", nanos).trim_end_matches('0'));
+    }
+    text.push('Z');
+    text
+}
+
+/// Days since the unix epoch for a proleptic gregorian date
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Proleptic gregorian date for a number of days since the unix epoch
+#[cfg_attr(not(any(feature = "chrono", feature = "time")), allow(dead_code))]
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn solr_date_round_trip() {
+        assert_eq!(parse_solr_date("1970-01-01T00:00:00Z"), Ok((0, 0)));
+        assert_eq!(
+            parse_solr_date("2006-11-30T00:00:00.25Z"),
+            Ok((1_164_844_800, 250_000_000))
+        );
+        assert_eq!(
+            format_solr_date(1_164_844_800, 250_000_000),
+            "2006-11-30T00:00:00.25Z"
+        );
+        assert_eq!(format_solr_date(951_782_400, 0), "2000-02-29T00:00:00Z");
+        assert!(parse_solr_date("2006-11-30").is_err());
+    }
+
+    #[test]
+    fn date_math_parsing() {
+        let expression: SolrDateMath = "2006-11-30T00:00:00Z+6MONTHS/MONTH".parse().unwrap();
+        assert_eq!(
+            expression,
+            SolrDateMath::from_date("2006-11-30T00:00:00Z")
+                .add(6, SolrDateUnit::Month)
+                .round(SolrDateUnit::Month)
+        );
+        assert!("NOW+DAY".parse::<SolrDateMath>().is_err());
+        assert!("NOW/3DAY".parse::<SolrDateMath>().is_err());
+    }
+}
@@ -0,0 +1,298 @@
+//! # Stellr Derive
+//!
+//! Derive macros for the `stellr` crate. These are re-exported by stellr when its `derive`
+//! feature is enabled, so this crate should not need to be used directly.
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, Lit, Meta, NestedMeta, Type};
+
+/// Derive `stellr::SolrDocument`, along with serde's Serialize and Deserialize, for a struct
+///
+/// Fields can be annotated with `#[solr(...)]` attributes:
+///
+/// * `rename = "name"` - the solr field name, if it differs from the struct field name
+/// * `unique_key` - marks the field holding the collection's uniqueKey, which may be an `Option`
+/// * `dynamic = "attr_*"` - collects all fields matching a dynamic field pattern into a map, keyed
+///   by the part of the name matched by the wildcard
+/// * `multi_valued` - accepts a single value as a one-element list (implied for `Vec` fields)
+/// * `default` - uses `Default::default()` when solr does not return the field (implied for `Vec`
+///   fields)
+/// * `skip` - leaves the field out of solr entirely, using `Default::default()` when deserialising
+#[proc_macro_derive(SolrDocument, attributes(solr))]
+pub fn derive_solr_document(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand_solr_document(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Field details gathered from the struct definition and its solr attributes
+struct SolrField {
+    ident: syn::Ident,
+    solr_name: String,
+    unique_key: bool,
+    dynamic: Option<String>,
+    multi_valued: bool,
+    default: bool,
+    optional: bool,
+    skip: bool,
+}
+
+fn expand_solr_document(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    if !input.generics.params.is_empty() {
+        return Err(Error::new(
+            input.generics.span(),
+            "SolrDocument cannot be derived for generic structs",
+        ));
+    }
+
+    let named_fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new(
+                    input.span(),
+                    "SolrDocument can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                input.span(),
+                "SolrDocument can only be derived for structs",
+            ))
+        }
+    };
+
+    let fields = named_fields
+        .iter()
+        .map(parse_field)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let unique_keys: Vec<&SolrField> = fields.iter().filter(|f| f.unique_key).collect();
+    if unique_keys.len() > 1 {
+        return Err(Error::new(
+            unique_keys[1].ident.span(),
+            "Only one field can be marked as the unique_key",
+        ));
+    }
+
+    let name = &input.ident;
+    let solr_fields: Vec<&String> = fields
+        .iter()
+        .filter(|f| !f.skip && f.dynamic.is_none())
+        .map(|f| &f.solr_name)
+        .collect();
+    let dynamic_patterns: Vec<&String> = fields.iter().filter_map(|f| f.dynamic.as_ref()).collect();
+
+    let (unique_key_field, unique_key_value) = match unique_keys.first() {
+        Some(field) => {
+            let solr_name = &field.solr_name;
+            let ident = &field.ident;
+            let value = if field.optional {
+                quote! { self.#ident.as_ref().map(::std::string::ToString::to_string) }
+            } else {
+                quote! { ::std::option::Option::Some(::std::string::ToString::to_string(&self.#ident)) }
+            };
+            (quote! { ::std::option::Option::Some(#solr_name) }, value)
+        }
+        None => (
+            quote! { ::std::option::Option::None },
+            quote! { ::std::option::Option::None },
+        ),
+    };
+
+    let serialize_entries = fields.iter().filter(|f| !f.skip).map(serialize_entry);
+    let named_bindings = fields
+        .iter()
+        .filter(|f| f.dynamic.is_none())
+        .map(deserialize_binding);
+    let dynamic_bindings = fields
+        .iter()
+        .filter(|f| f.dynamic.is_some())
+        .map(deserialize_binding);
+    let field_idents = fields.iter().map(|f| &f.ident);
+
+    Ok(quote! {
+        impl ::stellr::SolrDocument for #name {
+            fn solr_fields() -> &'static [&'static str] {
+                &[#(#solr_fields),*]
+            }
+
+            fn dynamic_field_patterns() -> &'static [&'static str] {
+                &[#(#dynamic_patterns),*]
+            }
+
+            fn unique_key_field() -> ::std::option::Option<&'static str> {
+                #unique_key_field
+            }
+
+            fn unique_key(&self) -> ::std::option::Option<::std::string::String> {
+                #unique_key_value
+            }
+        }
+
+        impl ::stellr::derive_support::serde::Serialize for #name {
+            fn serialize<__S>(&self, serializer: __S) -> ::std::result::Result<__S::Ok, __S::Error>
+            where
+                __S: ::stellr::derive_support::serde::Serializer,
+            {
+                use ::stellr::derive_support::serde::ser::SerializeMap;
+                let mut map = serializer.serialize_map(::std::option::Option::None)?;
+                #(#serialize_entries)*
+                map.end()
+            }
+        }
+
+        impl<'de> ::stellr::derive_support::serde::Deserialize<'de> for #name {
+            fn deserialize<__D>(deserializer: __D) -> ::std::result::Result<Self, __D::Error>
+            where
+                __D: ::stellr::derive_support::serde::Deserializer<'de>,
+            {
+                use ::stellr::derive_support::serde::de::Error;
+                let mut fields: ::stellr::derive_support::SolrFields =
+                    ::stellr::derive_support::serde::Deserialize::deserialize(deserializer)?;
+                #(#named_bindings)*
+                #(#dynamic_bindings)*
+                ::std::result::Result::Ok(#name { #(#field_idents),* })
+            }
+        }
+    })
+}
+
+fn parse_field(field: &syn::Field) -> Result<SolrField, Error> {
+    let ident = field.ident.clone().unwrap();
+    let type_name = outer_type_name(&field.ty);
+    let is_vec = type_name.as_deref() == Some("Vec");
+
+    let mut solr_field = SolrField {
+        solr_name: ident.to_string(),
+        ident,
+        unique_key: false,
+        dynamic: None,
+        multi_valued: is_vec,
+        default: is_vec,
+        optional: type_name.as_deref() == Some("Option"),
+        skip: false,
+    };
+
+    for attr in field.attrs.iter().filter(|a| a.path.is_ident("solr")) {
+        let meta_list = match attr.parse_meta()? {
+            Meta::List(meta_list) => meta_list,
+            other => return Err(Error::new(other.span(), "Expected #[solr(...)]")),
+        };
+
+        for nested in meta_list.nested {
+            match nested {
+                NestedMeta::Meta(Meta::NameValue(name_value)) => {
+                    let value = match &name_value.lit {
+                        Lit::Str(value) => value.value(),
+                        other => return Err(Error::new(other.span(), "Expected a string")),
+                    };
+                    if name_value.path.is_ident("rename") {
+                        solr_field.solr_name = value;
+                    } else if name_value.path.is_ident("dynamic") {
+                        if !value.starts_with('*') && !value.ends_with('*') {
+                            return Err(Error::new(
+                                name_value.lit.span(),
+                                "Dynamic field patterns must start or end with *",
+                            ));
+                        }
+                        solr_field.dynamic = Some(value);
+                    } else {
+                        return Err(Error::new(name_value.path.span(), "Unknown solr attribute"));
+                    }
+                }
+                NestedMeta::Meta(Meta::Path(path)) => {
+                    if path.is_ident("unique_key") {
+                        solr_field.unique_key = true;
+                    } else if path.is_ident("multi_valued") {
+                        solr_field.multi_valued = true;
+                    } else if path.is_ident("default") {
+                        solr_field.default = true;
+                    } else if path.is_ident("skip") {
+                        solr_field.skip = true;
+                    } else {
+                        return Err(Error::new(path.span(), "Unknown solr attribute"));
+                    }
+                }
+                other => return Err(Error::new(other.span(), "Unknown solr attribute")),
+            }
+        }
+    }
+
+    Ok(solr_field)
+}
+
+/// Name of the outermost type of a field (eg. "Option" for `Option<String>`)
+fn outer_type_name(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string()),
+        _ => None,
+    }
+}
+
+fn serialize_entry(field: &SolrField) -> TokenStream2 {
+    let ident = &field.ident;
+    let solr_name = &field.solr_name;
+
+    if let Some(pattern) = &field.dynamic {
+        quote! {
+            for (key, value) in &self.#ident {
+                map.serialize_entry(
+                    &::stellr::derive_support::dynamic_field_name(#pattern, key),
+                    value,
+                )?;
+            }
+        }
+    } else if field.optional {
+        quote! {
+            if let ::std::option::Option::Some(value) = &self.#ident {
+                map.serialize_entry(#solr_name, value)?;
+            }
+        }
+    } else {
+        quote! {
+            map.serialize_entry(#solr_name, &self.#ident)?;
+        }
+    }
+}
+
+fn deserialize_binding(field: &SolrField) -> TokenStream2 {
+    let ident = &field.ident;
+    let solr_name = &field.solr_name;
+    let multi_valued = field.multi_valued;
+
+    if field.skip {
+        quote! { let #ident = ::std::default::Default::default(); }
+    } else if let Some(pattern) = &field.dynamic {
+        quote! {
+            let #ident = ::stellr::derive_support::take_dynamic_fields(&mut fields, #pattern)
+                .map_err(__D::Error::custom)?;
+        }
+    } else if field.default {
+        quote! {
+            let #ident = ::stellr::derive_support::take_field_or_default(
+                &mut fields, #solr_name, #multi_valued,
+            )
+            .map_err(__D::Error::custom)?;
+        }
+    } else {
+        quote! {
+            let #ident = ::stellr::derive_support::take_field(
+                &mut fields, #solr_name, #multi_valued,
+            )
+            .map_err(__D::Error::custom)?;
+        }
+    }
+}
@@ -1,8 +1,30 @@
-use httpmock::Method::GET;
+use httpmock::Method::{DELETE, GET, POST, PUT};
 use httpmock::MockServer;
+use serde_json::json;
 
+use stellr::collections_admin::{
+    run_collections_action, submit_collections_action, SolrBackup, SolrBackupLocation,
+    SolrCreateCollection, SolrListBackup, SolrSplitShard,
+};
+use stellr::config_api::{SolrConfigCommands, SolrParamSetChanges};
+use stellr::core_admin::{run_core_admin_action, SolrCoreStatus, SolrSwapCores};
+use stellr::managed_resources::replace_stopwords;
+use stellr::metrics::{SolrMetricsGroup, SolrMetricsOptions};
 use stellr::prelude::*;
-use stellr::DirectSolrClient;
+use stellr::replication::{run_replication_command, SolrFetchIndex, SolrReplicationDetailsCommand};
+use stellr::response_types::{
+    SolrAsyncState, SolrAuthorizationType, SolrClusterStatusType, SolrConfigOverlayType,
+    SolrExtractOnlyType, SolrHealthType, SolrLukeType, SolrMetric, SolrMetricsType,
+    SolrParamSetsType, SolrPingType, SolrReplicaType, SolrSchemaType, SolrSystemInfoType,
+    SolrUpdateType,
+};
+use stellr::schema::{SolrSchemaChanges, SolrSchemaField};
+use stellr::schema_migration::{apply_schema_migration, SolrSchemaMigration};
+use stellr::security::{SolrAuthorizationCommands, SolrPermission};
+use stellr::{
+    bisect_update, DirectSolrClient, PayloadFormat, SolrExtractFormat, SolrExtractOptions,
+    SolrLukeOptions, SolrLukeShow,
+};
 
 use tokio::runtime::Runtime;
 
@@ -40,3 +62,939 @@ fn mock_test() {
         assert_eq!(10, docs_count);
     });
 }
+
+#[test]
+fn bisect_update_mock_test() {
+    let server = MockServer::start();
+
+    let poison_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/solr/films/update")
+            .body_contains("poison");
+        then.status(400)
+            .header("Content-Type", "application/json")
+            .body(r#"{ "responseHeader":{ "status":400, "QTime":1}, "error":{ "metadata":["error-class","org.apache.solr.common.SolrException"], "msg":"ERROR: [doc=poison] Error adding field", "code":400}}"#);
+    });
+    let update_mock = server.mock(|when, then| {
+        when.method(POST).path("/solr/films/update");
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .body(r#"{ "responseHeader":{ "status":0, "QTime":1}}"#);
+    });
+
+    let documents = vec![
+        json!({"id": "first"}),
+        json!({"id": "poison"}),
+        json!({"id": "third"}),
+        json!({"id": "fourth"}),
+    ];
+    let mut dead_letters = Vec::new();
+
+    let mut rt = Runtime::new().unwrap();
+    let rejected = rt.block_on(async {
+        let solr_client = DirectSolrClient::new(&server.url("/solr")).unwrap();
+        bisect_update(&solr_client, "films", &documents, |doc, response| {
+            dead_letters.push((doc.clone(), response.responseHeader.status))
+        })
+        .await
+        .expect("Bisected update failed")
+    });
+
+    assert_eq!(rejected, 1);
+    assert_eq!(dead_letters, vec![(json!({"id": "poison"}), 400)]);
+    // full batch, first half, "first", "poison", second half
+    poison_mock.assert_hits(3);
+    update_mock.assert_hits(2);
+}
+
+#[test]
+fn streaming_payload_mock_test() {
+    let server = MockServer::start();
+
+    let update_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/solr/films/update/json/docs")
+            .body("{\"id\":\"0\"}\n{\"id\":\"1\"}\n{\"id\":\"2\"}\n");
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .body(r#"{ "responseHeader":{ "status":0, "QTime":1}}"#);
+    });
+
+    let documents = (0..3).map(|i| json!({ "id": i.to_string() }));
+
+    let mut rt = Runtime::new().unwrap();
+    let result_struct = rt.block_on(async {
+        let solr_client = DirectSolrClient::new(&server.url("/solr")).unwrap();
+        solr_client
+            .update_json_docs("films")
+            .unwrap()
+            .payload_iter(documents, PayloadFormat::JsonLines)
+            .call::<SolrUpdateType>()
+            .await
+            .expect("Failed to parse")
+    });
+
+    update_mock.assert();
+    assert_eq!(result_struct.responseHeader.status, 0);
+}
+
+#[test]
+fn extract_only_mock_test() {
+    let server = MockServer::start();
+
+    let extract_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/solr/films/update/extract")
+            .query_param("extractOnly", "true")
+            .query_param("extractFormat", "text")
+            .body_contains("filename=\"notes.txt\"")
+            .body_contains("Some film notes");
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .body(r#"{ "responseHeader":{ "status":0, "QTime":12}, "notes.txt":"\nSome film notes\n", "notes.txt_metadata":["stream_size",["15"],"Content-Type",["text/plain; charset=ISO-8859-1"]]}"#);
+    });
+
+    let extract_options = SolrExtractOptions {
+        extract_only: Some(true),
+        extract_format: Some(SolrExtractFormat::Text),
+        ..Default::default()
+    };
+
+    let mut rt = Runtime::new().unwrap();
+    let result_struct = rt.block_on(async {
+        let solr_client = DirectSolrClient::new(&server.url("/solr")).unwrap();
+        solr_client
+            .extract("films", &extract_options)
+            .unwrap()
+            .multipart_file("notes.txt", b"Some film notes".to_vec())
+            .call::<SolrExtractOnlyType>()
+            .await
+            .expect("Failed to parse")
+    });
+
+    extract_mock.assert();
+    assert_eq!(result_struct.resource_name, "notes.txt");
+    assert_eq!(result_struct.content.trim(), "Some film notes");
+    assert_eq!(result_struct.metadata["stream_size"], vec!["15"]);
+}
+
+#[test]
+fn collections_admin_mock_test() {
+    let server = MockServer::start();
+
+    let create_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/solr/admin/collections")
+            .query_param("action", "CREATE")
+            .query_param("name", "films")
+            .query_param("numShards", "2")
+            .query_param("replicationFactor", "1");
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .body(r#"{ "responseHeader":{ "status":0, "QTime":1820}, "success":{ "10.0.0.1:8983_solr":{ "responseHeader":{ "status":0, "QTime":1203}, "core":"films_shard1_replica_n1"}, "10.0.0.2:8983_solr":{ "responseHeader":{ "status":0, "QTime":1205}, "core":"films_shard2_replica_n2"}}}"#);
+    });
+
+    let create = SolrCreateCollection {
+        replication_factor: Some(1),
+        ..SolrCreateCollection::new("films", 2)
+    };
+
+    let mut rt = Runtime::new().unwrap();
+    let response = rt.block_on(async {
+        let solr_client = DirectSolrClient::new(&server.url("/solr")).unwrap();
+        run_collections_action(&solr_client, &create)
+            .await
+            .expect("Failed to parse")
+    });
+
+    create_mock.assert();
+    assert!(response.is_success());
+    assert_eq!(
+        response.cores(),
+        vec!["films_shard1_replica_n1", "films_shard2_replica_n2"]
+    );
+}
+
+#[test]
+fn async_collections_admin_mock_test() {
+    let server = MockServer::start();
+
+    let submit_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/solr/admin/collections")
+            .query_param("action", "SPLITSHARD")
+            .query_param("shard", "shard1")
+            .query_param("async", "split-1");
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .body(r#"{ "responseHeader":{ "status":0, "QTime":12}, "requestid":"split-1"}"#);
+    });
+    let status_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/solr/admin/collections")
+            .query_param("action", "REQUESTSTATUS")
+            .query_param("requestid", "split-1");
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .body(r#"{ "responseHeader":{ "status":0, "QTime":1}, "success":{ "10.0.0.1:8983_solr":{ "responseHeader":{ "status":0, "QTime":0}}}, "status":{ "state":"completed", "msg":"found [split-1] in completed tasks"}}"#);
+    });
+    let delete_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/solr/admin/collections")
+            .query_param("action", "DELETESTATUS")
+            .query_param("requestid", "split-1");
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .body(r#"{ "responseHeader":{ "status":0, "QTime":1}, "status":"successfully removed stored response for [split-1]"}"#);
+    });
+
+    let split = SolrSplitShard::new("films", "shard1");
+
+    let mut rt = Runtime::new().unwrap();
+    let outcome = rt.block_on(async {
+        let solr_client = DirectSolrClient::new(&server.url("/solr")).unwrap();
+        let operation = submit_collections_action(&solr_client, &split, "split-1")
+            .await
+            .expect("Failed to submit");
+        let outcome = operation.wait().await.expect("Failed to poll");
+        operation.delete_status().await.expect("Failed to delete");
+        outcome
+    });
+
+    submit_mock.assert();
+    status_mock.assert();
+    delete_mock.assert();
+    assert_eq!(outcome.state, SolrAsyncState::Completed);
+    assert!(outcome.response.is_success());
+}
+
+#[test]
+fn cluster_status_mock_test() {
+    let server = MockServer::start();
+
+    let status_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/solr/admin/collections")
+            .query_param("action", "CLUSTERSTATUS");
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .body(r#"{ "responseHeader":{ "status":0, "QTime":3}, "cluster":{ "collections":{ "films":{ "pullReplicas":"0", "replicationFactor":"2", "shards":{ "shard1":{ "range":"80000000-ffffffff", "state":"active", "replicas":{ "core_node3":{ "core":"films_shard1_replica_n1", "base_url":"http://10.0.0.1:8983/solr", "node_name":"10.0.0.1:8983_solr", "state":"active", "type":"NRT", "force_set_state":"false", "leader":"true"}, "core_node5":{ "core":"films_shard1_replica_p2", "base_url":"http://10.0.0.2:8983/solr", "node_name":"10.0.0.2:8983_solr", "state":"down", "type":"PULL", "force_set_state":"false"}}, "health":"YELLOW"}}, "router":{ "name":"compositeId"}, "maxShardsPerNode":"-1", "autoAddReplicas":"false", "nrtReplicas":"1", "tlogReplicas":"0", "health":"YELLOW", "znodeVersion":11, "configName":"films", "aliases":["current_films"]}}, "aliases":{ "current_films":"films"}, "roles":{ "overseer":["10.0.0.1:8983_solr"]}, "live_nodes":["10.0.0.1:8983_solr", "10.0.0.2:8983_solr"]}}"#);
+    });
+
+    let mut rt = Runtime::new().unwrap();
+    let status = rt.block_on(async {
+        let solr_client = DirectSolrClient::new(&server.url("/solr")).unwrap();
+        solr_client
+            .cluster_status()
+            .unwrap()
+            .call::<SolrClusterStatusType>()
+            .await
+            .expect("Failed to parse")
+    });
+
+    status_mock.assert();
+    let films = status.cluster.collection("current_films").unwrap();
+    let leaders = films.leaders();
+    assert_eq!(leaders["shard1"].core, "films_shard1_replica_n1");
+    assert_eq!(
+        films.shards["shard1"].replicas["core_node5"].replica_type,
+        Some(SolrReplicaType::PULL)
+    );
+    assert_eq!(
+        status.cluster.unhealthy_replicas(),
+        vec![("films", "shard1", "core_node5")]
+    );
+    assert_eq!(status.cluster.roles["overseer"], vec!["10.0.0.1:8983_solr"]);
+}
+
+#[test]
+fn backup_mock_test() {
+    let server = MockServer::start();
+
+    let backup_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/solr/admin/collections")
+            .query_param("action", "BACKUP")
+            .query_param("collection", "films")
+            .query_param("name", "films_backup")
+            .query_param("location", "/var/backups/solr")
+            .query_param("incremental", "true")
+            .query_param("async", "backup-1");
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .body(r#"{ "responseHeader":{ "status":0, "QTime":8}, "requestid":"backup-1"}"#);
+    });
+    let status_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/solr/admin/collections")
+            .query_param("action", "REQUESTSTATUS")
+            .query_param("requestid", "backup-1");
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .body(r#"{ "responseHeader":{ "status":0, "QTime":1}, "response":{ "collection":"films", "numShards":2, "backupId":3, "indexVersion":"8.11.1", "startTime":"2021-06-01T10:00:00.000Z", "indexFileCount":40, "uploadedIndexFileCount":6, "endTime":"2021-06-01T10:00:02.000Z"}, "status":{ "state":"completed", "msg":"found [backup-1] in completed tasks"}}"#);
+    });
+    let list_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/solr/admin/collections")
+            .query_param("action", "LISTBACKUP")
+            .query_param("name", "films_backup");
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .body(r#"{ "responseHeader":{ "status":0, "QTime":4}, "collection":"films", "backups":[{ "indexFileCount":34, "indexSizeMB":1.2, "collection.configName":"films", "backupId":2, "collection":"films", "startTime":"2021-05-31T10:00:00.000Z", "indexVersion":"8.11.1"}, { "indexFileCount":40, "indexSizeMB":1.4, "collection.configName":"films", "backupId":3, "collection":"films", "startTime":"2021-06-01T10:00:00.000Z", "indexVersion":"8.11.1"}]}"#);
+    });
+
+    let location = SolrBackupLocation::new("films_backup", "/var/backups/solr");
+    let backup = SolrBackup {
+        incremental: Some(true),
+        ..SolrBackup::new("films", location.clone())
+    };
+
+    let mut rt = Runtime::new().unwrap();
+    let (outcome, backups) = rt.block_on(async {
+        let solr_client = DirectSolrClient::new(&server.url("/solr")).unwrap();
+        let operation = submit_collections_action(&solr_client, &backup, "backup-1")
+            .await
+            .expect("Failed to submit");
+        let outcome = operation.wait().await.expect("Failed to poll");
+        let backups = run_collections_action(&solr_client, &SolrListBackup::new(location))
+            .await
+            .expect("Failed to list");
+        (outcome, backups)
+    });
+
+    backup_mock.assert();
+    status_mock.assert();
+    list_mock.assert();
+    assert_eq!(outcome.response.response.unwrap().backupId, Some(3));
+    let backup_ids: Vec<Option<u32>> = backups.backups.iter().map(|b| b.backupId).collect();
+    assert_eq!(backup_ids, vec![Some(2), Some(3)]);
+}
+
+#[test]
+fn schema_mock_test() {
+    let server = MockServer::start();
+
+    let schema_mock = server.mock(|when, then| {
+        when.method(GET).path("/solr/films/schema");
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .body(r#"{ "responseHeader":{ "status":0, "QTime":2}, "schema":{ "name":"default-config", "version":1.6, "uniqueKey":"id", "fieldTypes":[{ "name":"string", "class":"solr.StrField", "sortMissingLast":true, "docValues":true}, { "name":"text_general", "class":"solr.TextField", "positionIncrementGap":"100", "multiValued":true, "indexAnalyzer":{ "tokenizer":{ "class":"solr.StandardTokenizerFactory"}, "filters":[{ "class":"solr.LowerCaseFilterFactory"}]}, "queryAnalyzer":{ "tokenizer":{ "class":"solr.StandardTokenizerFactory"}, "filters":[{ "class":"solr.SynonymGraphFilterFactory", "expand":"true", "ignoreCase":"true", "synonyms":"synonyms.txt"}, { "class":"solr.LowerCaseFilterFactory"}]}}], "fields":[{ "name":"id", "type":"string", "multiValued":false, "indexed":true, "required":true, "stored":true}, { "name":"name", "type":"text_general", "stored":true}], "dynamicFields":[{ "name":"*_txt", "type":"text_general", "indexed":true, "stored":true}], "copyFields":[{ "source":"name", "dest":"_text_"}]}}"#);
+    });
+
+    let update_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/solr/films/schema")
+            .header("Content-Type", "application/json")
+            .body(r#"{"add-field":{"multiValued":true,"name":"genre","type":"string"},"delete-field":{"name":"name"}}"#);
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .body(r#"{ "responseHeader":{ "status":0, "QTime":120}}"#);
+    });
+
+    let changes = SolrSchemaChanges::new()
+        .add_field(SolrSchemaField {
+            multi_valued: Some(true),
+            ..SolrSchemaField::new("genre", "string")
+        })
+        .unwrap()
+        .delete_field("name");
+
+    let mut rt = Runtime::new().unwrap();
+    let (schema, update) = rt.block_on(async {
+        let solr_client = DirectSolrClient::new(&server.url("/solr")).unwrap();
+        let schema = solr_client
+            .schema("films")
+            .unwrap()
+            .call::<SolrSchemaType>()
+            .await
+            .expect("Failed to parse");
+        let update = solr_client
+            .update_schema("films", &changes)
+            .unwrap()
+            .call::<SolrUpdateType>()
+            .await
+            .expect("Failed to parse");
+        (schema.schema, update)
+    });
+
+    schema_mock.assert();
+    update_mock.assert();
+    assert_eq!(schema.unique_key.as_deref(), Some("id"));
+    assert_eq!(schema.field("id").unwrap().required, Some(true));
+    assert_eq!(schema.dynamic_fields[0].name, "*_txt");
+    assert_eq!(schema.copy_fields[0].dest, "_text_");
+
+    let text_general = schema.field_type("text_general").unwrap();
+    assert_eq!(text_general.properties["positionIncrementGap"], "100");
+    let query_analyzer = text_general.query_analyzer.as_ref().unwrap();
+    assert_eq!(query_analyzer.filters[0].args["synonyms"], "synonyms.txt");
+    assert_eq!(update.responseHeader.status, 0);
+}
+
+#[test]
+fn schema_migration_mock_test() {
+    let server = MockServer::start();
+
+    let schema_mock = server.mock(|when, then| {
+        when.method(GET).path("/solr/films/schema");
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .body(r#"{ "responseHeader":{ "status":0, "QTime":2}, "schema":{ "uniqueKey":"id", "fieldTypes":[{ "name":"string", "class":"solr.StrField", "sortMissingLast":true}], "fields":[{ "name":"id", "type":"string", "required":true}]}}"#);
+    });
+
+    let update_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/solr/films/schema")
+            .body(r#"{"add-field":{"multiValued":true,"name":"genre","type":"string"}}"#);
+        then.status(400)
+            .header("Content-Type", "application/json")
+            .body(r#"{ "responseHeader":{ "status":400, "QTime":3}, "error":{ "metadata":["error-class","org.apache.solr.api.ApiBag$ExceptionWithErrObject"], "details":[{ "add-field":{ "name":"genre", "type":"string", "multiValued":true}, "errorMessages":["Field 'genre' already exists.\n"]}], "msg":"error processing commands", "code":400}}"#);
+    });
+
+    let migration = SolrSchemaMigration {
+        fields: vec![SolrSchemaField {
+            multi_valued: Some(true),
+            ..SolrSchemaField::new("genre", "string")
+        }],
+        ..Default::default()
+    };
+
+    let mut rt = Runtime::new().unwrap();
+    let result = rt.block_on(async {
+        let solr_client = DirectSolrClient::new(&server.url("/solr")).unwrap();
+        apply_schema_migration(&solr_client, "films", &migration).await
+    });
+
+    schema_mock.assert();
+    update_mock.assert();
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Schema update failed: error processing commands; Field 'genre' already exists."
+    );
+}
+
+#[test]
+fn config_api_mock_test() {
+    let server = MockServer::start();
+
+    let update_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/solr/films/config")
+            .body(r#"{"set-property":{"updateHandler.autoCommit.maxTime":15000}}"#);
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .body(r#"{ "responseHeader":{ "status":0, "QTime":40}, "WARNING":"This response format is experimental.  It is likely to change in the future."}"#);
+    });
+
+    let overlay_mock = server.mock(|when, then| {
+        when.method(GET).path("/solr/films/config/overlay");
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .body(r#"{ "responseHeader":{ "status":0, "QTime":1}, "overlay":{ "znodeVersion":2, "props":{ "updateHandler":{ "autoCommit":{ "maxTime":15000}}}, "requestHandler":{ "/films":{ "name":"/films", "class":"solr.SearchHandler"}}}}"#);
+    });
+
+    let params_update_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/solr/films/config/params")
+            .body(r#"{"set":{"genre_facets":{"facet":"true","facet.field":"genre"}}}"#);
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .body(r#"{ "responseHeader":{ "status":0, "QTime":12}}"#);
+    });
+
+    let params_mock = server.mock(|when, then| {
+        when.method(GET).path("/solr/films/config/params");
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .body(r#"{ "responseHeader":{ "status":0, "QTime":0}, "response":{ "znodeVersion":1, "params":{ "genre_facets":{ "facet":"true", "facet.field":"genre", "":{ "v":0}}}}}"#);
+    });
+
+    let select_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/solr/films/select")
+            .query_param("useParams", "genre_facets");
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .body(r#"{ "responseHeader":{ "status":0, "QTime":1}, "response":{ "numFound":0, "start":0, "docs":[]}}"#);
+    });
+
+    let mut rt = Runtime::new().unwrap();
+    let (overlay, param_sets) = rt.block_on(async {
+        let solr_client = DirectSolrClient::new(&server.url("/solr")).unwrap();
+        solr_client
+            .update_config(
+                "films",
+                &SolrConfigCommands::new().set_property("updateHandler.autoCommit.maxTime", 15000),
+            )
+            .unwrap()
+            .call::<SolrUpdateType>()
+            .await
+            .expect("Failed to parse");
+        let overlay = solr_client
+            .config_overlay("films")
+            .unwrap()
+            .call::<SolrConfigOverlayType>()
+            .await
+            .expect("Failed to parse");
+        solr_client
+            .update_params(
+                "films",
+                &SolrParamSetChanges::new().set(
+                    "genre_facets",
+                    json!({"facet": "true", "facet.field": "genre"}),
+                ),
+            )
+            .unwrap()
+            .call::<SolrUpdateType>()
+            .await
+            .expect("Failed to parse");
+        let param_sets = solr_client
+            .config_params("films")
+            .unwrap()
+            .call::<SolrParamSetsType>()
+            .await
+            .expect("Failed to parse");
+        solr_client
+            .select("films")
+            .unwrap()
+            .use_params("genre_facets")
+            .unstructured_call()
+            .await
+            .expect("Failed to parse");
+        (overlay.overlay, param_sets.response)
+    });
+
+    update_mock.assert();
+    overlay_mock.assert();
+    params_update_mock.assert();
+    params_mock.assert();
+    select_mock.assert();
+    assert_eq!(
+        overlay.props["updateHandler"]["autoCommit"]["maxTime"],
+        15000
+    );
+    assert_eq!(
+        overlay.component("requestHandler", "/films").unwrap()["class"],
+        "solr.SearchHandler"
+    );
+    let genre_facets = &param_sets.params["genre_facets"];
+    assert_eq!(genre_facets.params["facet.field"], "genre");
+    assert_eq!(genre_facets.version.as_ref().unwrap().v, 0);
+}
+
+#[test]
+fn core_admin_mock_test() {
+    let server = MockServer::start();
+
+    let swap_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/solr/admin/cores")
+            .query_param("action", "SWAP")
+            .query_param("core", "films")
+            .query_param("other", "films_rebuilt");
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .body(r#"{ "responseHeader":{ "status":0, "QTime":35}}"#);
+    });
+
+    let status_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/solr/admin/cores")
+            .query_param("action", "STATUS")
+            .query_param("core", "films");
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .body(r#"{ "responseHeader":{ "status":0, "QTime":4}, "initFailures":{}, "status":{ "films":{ "name":"films", "instanceDir":"/var/solr/data/films", "dataDir":"/var/solr/data/films/data/", "config":"solrconfig.xml", "schema":"managed-schema", "startTime":"2020-10-01T09:12:30.123Z", "uptime":86400, "index":{ "numDocs":1100, "maxDoc":1104, "deletedDocs":4, "indexHeapUsageBytes":-1, "version":38, "segmentCount":3, "current":true, "hasDeletions":true, "directory":"org.apache.lucene.store.NRTCachingDirectory:NRTCachingDirectory(MMapDirectory@/var/solr/data/films/data/index)", "segmentsFile":"segments_5", "segmentsFileSizeInBytes":312, "userData":{ "commitCommandVer":"1679449202836127744", "commitTimeMSec":"1601543550123"}, "lastModified":"2020-10-01T09:12:30.123Z", "sizeInBytes":1048576, "size":"1 MB"}}}}"#);
+    });
+
+    let mut rt = Runtime::new().unwrap();
+    let (swap, status) = rt.block_on(async {
+        let solr_client = DirectSolrClient::new(&server.url("/solr")).unwrap();
+        let swap =
+            run_core_admin_action(&solr_client, &SolrSwapCores::new("films", "films_rebuilt"))
+                .await
+                .expect("Failed to parse");
+        let status = run_core_admin_action(&solr_client, &SolrCoreStatus::core("films"))
+            .await
+            .expect("Failed to parse");
+        (swap, status)
+    });
+
+    swap_mock.assert();
+    status_mock.assert();
+    assert!(swap.is_success());
+
+    let index = status.core("films").unwrap().index.as_ref().unwrap();
+    assert_eq!(index.numDocs, Some(1100));
+    assert_eq!(index.deletedDocs, Some(4));
+    assert_eq!(index.userData["commitTimeMSec"], "1601543550123");
+    assert!(status.core("missing").is_none());
+}
+
+#[test]
+fn replication_mock_test() {
+    let server = MockServer::start();
+
+    let details_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/solr/films/replication")
+            .query_param("command", "details");
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .body(r#"{ "responseHeader":{ "status":0, "QTime":2}, "details":{ "indexSize":"1.02 MB", "indexPath":"/var/solr/data/films/data/index/", "commits":[[ "indexVersion",1601543550123, "generation",5, "filelist",["segments_5"]]], "isMaster":"false", "isSlave":"true", "indexVersion":1601543550123, "generation":5, "slave":{ "masterUrl":"http://solr-leader:8983/solr/films", "pollInterval":"00:00:60", "nextExecutionAt":"Thu Oct 01 09:13:30 UTC 2020", "indexReplicatedAt":"Thu Oct 01 09:12:30 UTC 2020", "timesIndexReplicated":3, "lastCycleBytesDownloaded":1048576, "previousCycleTimeInSeconds":1, "isPollingDisabled":"false", "isReplicating":"false"}}}"#);
+    });
+
+    let fetch_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/solr/films/replication")
+            .query_param("command", "fetchindex")
+            .query_param("leaderUrl", "http://solr-other:8983/solr/films/replication");
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .body(r#"{ "responseHeader":{ "status":0, "QTime":1}, "status":"OK"}"#);
+    });
+
+    let mut rt = Runtime::new().unwrap();
+    let (details, fetch) = rt.block_on(async {
+        let solr_client = DirectSolrClient::new(&server.url("/solr")).unwrap();
+        let details =
+            run_replication_command(&solr_client, "films", &SolrReplicationDetailsCommand)
+                .await
+                .expect("Failed to parse");
+        let fetch_index = SolrFetchIndex {
+            leader_url: Some(String::from(
+                "http://solr-other:8983/solr/films/replication",
+            )),
+            ..Default::default()
+        };
+        let fetch = run_replication_command(&solr_client, "films", &fetch_index)
+            .await
+            .expect("Failed to parse");
+        (details, fetch)
+    });
+
+    details_mock.assert();
+    fetch_mock.assert();
+    assert!(fetch.is_ok());
+
+    let details = details.details;
+    assert!(details.is_follower());
+    assert!(!details.is_leader());
+    assert_eq!(details.generation, Some(5));
+    let follower = details.follower.unwrap();
+    assert_eq!(
+        follower.leaderUrl.as_deref(),
+        Some("http://solr-leader:8983/solr/films")
+    );
+    assert_eq!(follower.timesIndexReplicated, Some(3));
+    assert!(!follower.is_polling_disabled());
+    assert_eq!(follower.other["previousCycleTimeInSeconds"], json!(1));
+}
+
+#[test]
+fn security_mock_test() {
+    let server = MockServer::start();
+
+    let read_mock = server.mock(|when, then| {
+        when.method(GET).path("/solr/admin/authorization");
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .body(r#"{ "responseHeader":{ "status":0, "QTime":0}, "authorization.enabled":true, "authorization":{ "class":"solr.RuleBasedAuthorizationPlugin", "permissions":[{ "name":"read", "role":"*", "index":1}, { "name":"update", "role":["admin"], "index":2}], "user-role":{ "solr":"admin"}, "":{ "v":7}}}"#);
+    });
+
+    let update_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/solr/admin/authorization")
+            .header("Content-Type", "application/json")
+            .body(r#"{"set-user-role":{"indexer":["update"]},"update-permission":{"index":2,"name":"update","role":["admin","update"]}}"#);
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .body(r#"{ "responseHeader":{ "status":0, "QTime":12}}"#);
+    });
+
+    let mut rt = Runtime::new().unwrap();
+    let (current, updated) = rt.block_on(async {
+        let solr_client = DirectSolrClient::new(&server.url("/solr")).unwrap();
+        let current = solr_client
+            .authorization()
+            .unwrap()
+            .call::<SolrAuthorizationType>()
+            .await
+            .expect("Failed to parse");
+
+        let index = current
+            .authorization
+            .as_ref()
+            .unwrap()
+            .permission("update")
+            .unwrap()
+            .index
+            .unwrap();
+        let commands = SolrAuthorizationCommands::new()
+            .set_user_role("indexer", &["update"])
+            .update_permission(
+                index,
+                SolrPermission::predefined("update", &["admin", "update"]),
+            )
+            .unwrap();
+        let updated = solr_client
+            .update_authorization(&commands)
+            .unwrap()
+            .call::<SolrUpdateType>()
+            .await
+            .expect("Failed to parse");
+        (current, updated)
+    });
+
+    read_mock.assert();
+    update_mock.assert();
+    assert_eq!(current.authorizationEnabled, Some(true));
+    assert_eq!(current.authorization.unwrap().roles("solr"), ["admin"]);
+    assert_eq!(updated.responseHeader.status, 0);
+}
+
+#[test]
+fn managed_stopwords_mock_test() {
+    let server = MockServer::start();
+
+    let read_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/solr/films/schema/analysis/stopwords/english");
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .body(r#"{ "responseHeader":{ "status":0, "QTime":1}, "wordSet":{ "initArgs":{ "ignoreCase":true}, "initializedOn":"2020-10-01T09:12:30.123Z", "updatedSinceInit":"2020-10-02T10:00:00.000Z", "managedList":["a", "an", "the"]}}"#);
+    });
+
+    let delete_mock = server.mock(|when, then| {
+        when.method(DELETE)
+            .path("/solr/films/schema/analysis/stopwords/english/an");
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .body(r#"{ "responseHeader":{ "status":0, "QTime":3}}"#);
+    });
+
+    let add_mock = server.mock(|when, then| {
+        when.method(PUT)
+            .path("/solr/films/schema/analysis/stopwords/english")
+            .body(r#"["of"]"#);
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .body(r#"{ "responseHeader":{ "status":0, "QTime":3}}"#);
+    });
+
+    let reload_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/solr/admin/collections")
+            .query_param("action", "RELOAD")
+            .query_param("name", "films");
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .body(r#"{ "responseHeader":{ "status":0, "QTime":850}, "success":{ "192.168.1.2:8983_solr":{ "responseHeader":{ "status":0, "QTime":800}}}}"#);
+    });
+
+    let mut rt = Runtime::new().unwrap();
+    let changes = rt.block_on(async {
+        let solr_client = DirectSolrClient::new(&server.url("/solr")).unwrap();
+        replace_stopwords(&solr_client, "films", "english", &["a", "The", "Of"])
+            .await
+            .expect("Failed to replace stopwords")
+    });
+
+    read_mock.assert();
+    delete_mock.assert();
+    add_mock.assert();
+    reload_mock.assert();
+    assert_eq!(changes.delete, vec!["an"]);
+    assert_eq!(changes.add, vec!["of"]);
+}
+
+#[test]
+fn luke_mock_test() {
+    let server = MockServer::start();
+
+    let fields_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/solr/films/admin/luke")
+            .query_param("fl", "genre,name,plot,summary,year")
+            .query_param("numTerms", "2");
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .body(r#"{ "responseHeader":{ "status":0, "QTime":12}, "index":{ "numDocs":1100, "maxDoc":1104, "deletedDocs":4, "indexHeapUsageBytes":-1, "version":38, "segmentCount":3, "current":true, "hasDeletions":true, "directory":"org.apache.lucene.store.NRTCachingDirectory:NRTCachingDirectory(MMapDirectory@/var/solr/data/films/data/index)", "segmentsFile":"segments_5", "segmentsFileSizeInBytes":312, "userData":{ "commitTimeMSec":"1601543550123"}, "lastModified":"2020-10-01T09:12:30.123Z"}, "fields":{ "genre":{ "type":"string", "schema":"I-SD-M------OF-----l", "dynamicBase":null, "index":"I-S---------OF-----", "docs":1100, "distinct":208, "topTerms":["Drama", 552, "Comedy", 389], "histogram":["1", 104, "2", 30]}, "name":{ "type":"text_general", "schema":"ITS-U-----------", "index":"(unstored field)", "docs":1100}, "plot":{ "type":"text_general", "schema":"ITS-U-Vop---------", "index":"ITS---V-----------", "docs":1100}, "summary":{ "type":"text_general", "schema":"ITS-U-V-----------", "index":"ITS---------------", "docs":650}, "year":{ "type":"pint", "schema":"I-SDU-----OF------", "index":"-TS---------------", "docs":1100}}, "info":{ "key":{ "I":"Indexed", "S":"Stored"}}}"#);
+    });
+
+    let doc_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/solr/films/admin/luke")
+            .query_param("show", "doc")
+            .query_param("id", "/en/45_2006");
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .body(r#"{ "responseHeader":{ "status":0, "QTime":3}, "doc":{ "docId":0, "lucene":{ "id":{ "type":"string", "schema":"I-S-U-----OF-----l", "flags":"I-S-------OF------", "value":"/en/45_2006", "internal":"/en/45_2006", "docFreq":1}, "genre":{ "type":"string", "schema":"I-SD-M------OF-----l", "flags":"I-S---------OF-----", "value":"Black comedy", "internal":"Black comedy", "docFreq":12}, "genre":{ "type":"string", "schema":"I-SD-M------OF-----l", "flags":"I-S---------OF-----", "value":"Thriller", "internal":"Thriller", "docFreq":160}}, "solr":{ "id":"/en/45_2006", "genre":["Black comedy", "Thriller"]}}, "info":{}}"#);
+    });
+
+    let mut rt = Runtime::new().unwrap();
+    let (fields, doc) = rt.block_on(async {
+        let solr_client = DirectSolrClient::new(&server.url("/solr")).unwrap();
+        let field_options = SolrLukeOptions {
+            fields: vec![
+                String::from("genre"),
+                String::from("name"),
+                String::from("plot"),
+                String::from("summary"),
+                String::from("year"),
+            ],
+            num_terms: Some(2),
+            ..Default::default()
+        };
+        let fields = solr_client
+            .luke("films", &field_options)
+            .unwrap()
+            .call::<SolrLukeType>()
+            .await
+            .expect("Failed to parse");
+
+        let doc_options = SolrLukeOptions {
+            show: Some(SolrLukeShow::Doc),
+            id: Some(String::from("/en/45_2006")),
+            ..Default::default()
+        };
+        let doc = solr_client
+            .luke("films", &doc_options)
+            .unwrap()
+            .call::<SolrLukeType>()
+            .await
+            .expect("Failed to parse");
+        (fields, doc)
+    });
+
+    fields_mock.assert();
+    doc_mock.assert();
+
+    assert_eq!(fields.index.as_ref().unwrap().numDocs, Some(1100));
+    let genre = &fields.fields["genre"];
+    assert_eq!(genre.distinct, Some(208));
+    assert_eq!(
+        genre.topTerms,
+        vec![(String::from("Drama"), 552), (String::from("Comedy"), 389)]
+    );
+    assert!(genre.schema_mismatches().is_empty());
+    assert!(fields.fields["name"].schema_mismatches().is_empty());
+    // term vector offsets and positions, and a point field's flags, can't be read back
+    assert!(fields.fields["plot"].schema_mismatches().is_empty());
+    assert!(fields.fields["year"].schema_mismatches().is_empty());
+    // summary gained termVectors after its documents were indexed
+    assert_eq!(
+        fields.schema_mismatches(),
+        vec![("summary", vec!['V'])].into_iter().collect()
+    );
+
+    let doc = doc.doc.unwrap();
+    assert_eq!(doc.lucene["genre"].len(), 2);
+    assert_eq!(doc.lucene["genre"][1].value.as_deref(), Some("Thriller"));
+    assert_eq!(doc.lucene["id"][0].docFreq, Some(1));
+}
+
+#[test]
+fn metrics_mock_test() {
+    let server = MockServer::start();
+
+    let metrics_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/solr/admin/metrics")
+            .query_param("group", "core")
+            .query_param("prefix", "QUERY./select.requestTimes");
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .body(r#"{ "responseHeader":{ "status":0, "QTime":5}, "metrics":{ "solr.core.films.shard1.replica_n1":{ "QUERY./select.requestTimes":{ "count":12, "meanRate":0.02, "1minRate":0.01, "5minRate":0.015, "15minRate":0.012, "min_ms":0.8, "max_ms":45.2, "mean_ms":6.1, "median_ms":4.3, "stddev_ms":5.5, "p75_ms":7.9, "p95_ms":18.4, "p99_ms":45.2, "p999_ms":45.2}}}}"#);
+    });
+
+    let options = SolrMetricsOptions {
+        groups: vec![SolrMetricsGroup::Core],
+        prefixes: vec![String::from("QUERY./select.requestTimes")],
+        ..Default::default()
+    };
+
+    let mut rt = Runtime::new().unwrap();
+    let metrics = rt.block_on(async {
+        let solr_client = DirectSolrClient::new(&server.url("/solr")).unwrap();
+        solr_client
+            .metrics(&options)
+            .unwrap()
+            .call::<SolrMetricsType>()
+            .await
+            .expect("Failed to parse")
+    });
+
+    metrics_mock.assert();
+    match metrics.metric(
+        "solr.core.films.shard1.replica_n1",
+        "QUERY./select.requestTimes",
+    ) {
+        Some(SolrMetric::Timer(timer)) => {
+            assert_eq!(timer.count, 12);
+            assert_eq!(timer.p95_ms, 18.4);
+        }
+        other => panic!("Expected a timer, found {:?}", other),
+    }
+}
+
+#[test]
+fn health_mock_test() {
+    let server = MockServer::start();
+
+    let ping_mock = server.mock(|when, then| {
+        when.method(GET).path("/solr/films/admin/ping");
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .body(r#"{ "responseHeader":{ "zkConnected":true, "status":0, "QTime":2, "params":{ "q":"{!lucene}*:*", "distrib":"false", "df":"_text_", "rows":"10", "echoParams":"all"}}, "status":"OK"}"#);
+    });
+
+    let health_mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/solr/admin/info/health")
+            .query_param("requireHealthyCores", "true");
+        then.status(503)
+            .header("Content-Type", "application/json")
+            .body(r#"{ "responseHeader":{ "status":503, "QTime":0}, "status":"FAILURE", "message":"Replica(s) [films_shard1_replica_n1] are currently initializing or recovering", "error":{ "metadata":["error-class","org.apache.solr.common.SolrException","root-error-class","org.apache.solr.common.SolrException"], "msg":"Replica(s) [films_shard1_replica_n1] are currently initializing or recovering", "code":503}}"#);
+    });
+
+    let system_mock = server.mock(|when, then| {
+        when.method(GET).path("/solr/admin/info/system");
+        then.status(200)
+            .header("Content-Type", "application/json")
+            .body(r#"{ "responseHeader":{ "status":0, "QTime":18}, "mode":"solrcloud", "zkHost":"zookeeper:2181", "solr_home":"/var/solr/data", "core_root":"/var/solr/data", "node":"10.0.0.1:8983_solr", "lucene":{ "solr-spec-version":"8.6.2", "solr-impl-version":"8.6.2 016993b65e393b58246d54e8ddda9f56a453eb0e - ishan - 2020-08-26 13:31:47", "lucene-spec-version":"8.6.2", "lucene-impl-version":"8.6.2 016993b65e393b58246d54e8ddda9f56a453eb0e - ishan - 2020-08-26 13:25:40"}, "jvm":{ "version":"11.0.8 11.0.8+10", "name":"Oracle Corporation OpenJDK 64-Bit Server VM", "spec":{ "vendor":"Oracle Corporation", "name":"Java Platform API Specification", "version":"11"}, "processors":4, "memory":{ "free":"389.3 MB", "total":"512 MB", "max":"512 MB", "used":"122.7 MB (%24)", "raw":{ "free":408198656, "total":536870912, "max":536870912, "used":128672256, "used%":23.967}}}, "system":{ "name":"Linux", "arch":"amd64", "availableProcessors":4, "systemLoadAverage":0.52}}"#);
+    });
+
+    let mut rt = Runtime::new().unwrap();
+    let (ping, health, system) = rt.block_on(async {
+        let solr_client = DirectSolrClient::new(&server.url("/solr")).unwrap();
+        let ping = solr_client
+            .ping("films")
+            .unwrap()
+            .call::<SolrPingType>()
+            .await
+            .expect("Failed to parse");
+        let health = solr_client
+            .health(true)
+            .unwrap()
+            .call::<SolrHealthType>()
+            .await
+            .expect("Failed to parse");
+        let system = solr_client
+            .system_info()
+            .unwrap()
+            .call::<SolrSystemInfoType>()
+            .await
+            .expect("Failed to parse");
+        (ping, health, system)
+    });
+
+    ping_mock.assert();
+    health_mock.assert();
+    system_mock.assert();
+    assert!(ping.is_ok());
+    assert!(!health.is_healthy());
+    assert_eq!(system.mode.as_deref(), Some("solrcloud"));
+    assert_eq!(system.lucene.solrSpecVersion, "8.6.2");
+    assert_eq!(system.jvm.memory.raw.max, 536870912);
+}
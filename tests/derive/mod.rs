@@ -0,0 +1,92 @@
+use serde_json::json;
+use std::collections::HashMap;
+use stellr::SolrDocument;
+
+#[derive(Debug, PartialEq, SolrDocument)]
+struct Product {
+    #[solr(unique_key)]
+    id: String,
+    #[solr(rename = "inStock")]
+    in_stock: bool,
+    popularity: Option<u32>,
+    features: Vec<String>,
+    #[solr(dynamic = "attr_*")]
+    attributes: HashMap<String, Vec<String>>,
+    #[solr(skip)]
+    local_note: String,
+}
+
+#[test]
+fn derived_field_list() {
+    assert_eq!(Product::fl(), "id,inStock,popularity,features,attr_*");
+    assert_eq!(Product::unique_key_field(), Some("id"));
+}
+
+#[test]
+fn derived_deserialize() {
+    let document = json!({
+        "id": "SP2514N",
+        "inStock": true,
+        "features": "7200RPM",
+        "attr_colour": ["black"],
+        "_version_": 1683174404568121344u64
+    });
+    let product: Product = serde_json::from_value(document).unwrap();
+
+    let mut attributes = HashMap::new();
+    attributes.insert(String::from("colour"), vec![String::from("black")]);
+    assert_eq!(
+        product,
+        Product {
+            id: String::from("SP2514N"),
+            in_stock: true,
+            popularity: None,
+            features: vec![String::from("7200RPM")],
+            attributes,
+            local_note: String::new(),
+        }
+    );
+    assert_eq!(product.unique_key(), Some(String::from("SP2514N")));
+}
+
+#[test]
+fn derived_serialize() {
+    let mut attributes = HashMap::new();
+    attributes.insert(String::from("colour"), vec![String::from("black")]);
+    let product = Product {
+        id: String::from("SP2514N"),
+        in_stock: false,
+        popularity: None,
+        features: vec![],
+        attributes,
+        local_note: String::from("not indexed"),
+    };
+
+    assert_eq!(
+        serde_json::to_value(&product).unwrap(),
+        json!({"id": "SP2514N", "inStock": false, "features": [], "attr_colour": ["black"]})
+    );
+}
+
+#[derive(Debug, PartialEq, SolrDocument)]
+struct Draft {
+    #[solr(unique_key)]
+    id: Option<String>,
+    title: String,
+}
+
+#[test]
+fn derived_optional_unique_key() {
+    let draft = Draft {
+        id: None,
+        title: String::from("Untitled"),
+    };
+    assert_eq!(Draft::unique_key_field(), Some("id"));
+    assert_eq!(draft.unique_key(), None);
+
+    let saved = Draft {
+        id: Some(String::from("draft-1")),
+        ..draft
+    };
+    assert_eq!(saved.unique_key(), Some(String::from("draft-1")));
+}
@@ -8,3 +8,6 @@ mod asynchronous;
 
 #[cfg(feature = "blocking")]
 mod blocking;
+
+#[cfg(feature = "derive")]
+mod derive;